@@ -0,0 +1,145 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use aleluia::basis::BasisTable;
+use aleluia::generator::Generator;
+use aleluia::object::Object;
+use aleluia::types::Mat4x1;
+
+/// Função de base da spline recursiva.
+///
+/// Implementação de referência, que avalia uma função de base de cada vez; usada para
+/// comparação com `BasisTable`.
+fn spline_blend(k: usize, t: usize, u: &[f32], v: f32) -> f32 {
+    if t == 1 {
+        if u[k] <= v && v < u[k + 1] {
+            1.0
+        } else {
+            0.0
+        }
+    } else {
+        let mut value = 0.0;
+        let denom1 = u[k + t - 1] - u[k];
+        let denom2 = u[k + t] - u[k + 1];
+
+        if denom1 != 0.0 {
+            value += ((v - u[k]) / denom1) * spline_blend(k, t - 1, u, v);
+        }
+        if denom2 != 0.0 {
+            value += ((u[k + t] - v) / denom2) * spline_blend(k + 1, t - 1, u, v);
+        }
+        value
+    }
+}
+
+/// Parâmetros uniformes no domínio dos nós, como em `Object::gen_mesh`.
+fn parameters(knots: &[f32], n: usize, t: usize, res: usize) -> Vec<f32> {
+    let start = knots[t - 1];
+    let end = knots[n + 1].next_down();
+    (0..res)
+        .map(|k| start + (end - start) * k as f32 / (res - 1) as f32)
+        .collect()
+}
+
+/// Avaliação anterior da superfície: todas as funções de base recursivas para cada vértice.
+fn recursive_mesh(object: &Object) -> Vec<Mat4x1> {
+    let (ni, nj) = object.get_size();
+    let (ti, tj) = object.get_orders();
+    let (resi, resj) = object.get_resolution();
+    let knots_i = object.get_knots_i();
+    let knots_j = object.get_knots_j();
+    let parameters_i = parameters(knots_i, ni, ti, resi);
+    let parameters_j = parameters(knots_j, nj, tj, resj);
+
+    let mut vertices = vec![Mat4x1::zeros(); resi * resj];
+    for (i, &u) in parameters_i.iter().enumerate() {
+        for (j, &v) in parameters_j.iter().enumerate() {
+            let vertex = &mut vertices[i * resj + j];
+            for ki in 0..=ni {
+                for kj in 0..=nj {
+                    let blend = spline_blend(ki, ti, knots_i, u) * spline_blend(kj, tj, knots_j, v);
+                    *vertex += object.control_points[ki * (nj + 1) + kj] * blend;
+                }
+            }
+            *vertex /= vertex.w;
+        }
+    }
+    vertices
+}
+
+/// Avaliação da superfície pelas tabelas de funções de base, como em `Object::gen_mesh`, mas em
+/// uma única thread e sem faces, triângulos e normais, para comparação com `recursive_mesh`.
+fn table_mesh(object: &Object) -> Vec<Mat4x1> {
+    let (ni, nj) = object.get_size();
+    let (ti, tj) = object.get_orders();
+    let (resi, resj) = object.get_resolution();
+    let knots_i = object.get_knots_i();
+    let knots_j = object.get_knots_j();
+    let basis_i = BasisTable::new(knots_i, ni, ti, &parameters(knots_i, ni, ti, resi));
+    let basis_j = BasisTable::new(knots_j, nj, tj, &parameters(knots_j, nj, tj, resj));
+
+    let mut vertices = vec![Mat4x1::zeros(); resi * resj];
+    for i in 0..resi {
+        let (first_i, values_i) = basis_i.get(i);
+        for j in 0..resj {
+            let (first_j, values_j) = basis_j.get(j);
+            let vertex = &mut vertices[i * resj + j];
+            for (a, &value_i) in values_i.iter().enumerate() {
+                for (b, &value_j) in values_j.iter().enumerate() {
+                    *vertex += object.control_points[(first_i + a) * (nj + 1) + first_j + b] * (value_i * value_j);
+                }
+            }
+            *vertex /= vertex.w;
+        }
+    }
+    vertices
+}
+
+fn bench_basis(c: &mut Criterion) {
+    let mut group = c.benchmark_group("funcoes_de_base");
+    for (n, t) in [(10, 3), (20, 4), (40, 5)] {
+        let object = Object::from_generator(&Generator::Plane { height: 0.0 }, n, n, t, t, 2, 2).unwrap();
+        let knots = object.get_knots_i().to_vec();
+        let parameters = parameters(&knots, n, t, 100);
+        let label = format!("n{n}_t{t}");
+
+        group.bench_with_input(BenchmarkId::new("recursiva", &label), &parameters, |b, parameters| {
+            b.iter(|| {
+                parameters
+                    .iter()
+                    .map(|&u| (0..=n).map(|k| spline_blend(k, t, &knots, u)).sum::<f32>())
+                    .sum::<f32>()
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("tabela", &label), &parameters, |b, parameters| {
+            b.iter(|| BasisTable::new(&knots, n, t, black_box(parameters)))
+        });
+    }
+    group.finish();
+}
+
+fn bench_mesh(c: &mut Criterion) {
+    let mut group = c.benchmark_group("malha");
+    group.sample_size(10);
+    for (n, t, res) in [(10, 3, 50), (20, 4, 100)] {
+        let generator = Generator::Random { seed: 0, amplitude: 10.0 };
+        let object = Object::from_generator(&generator, n, n, t, t, res, res).unwrap();
+        let label = format!("n{n}_t{t}_res{res}");
+
+        // As duas avaliações devem produzir os mesmos vértices de `Object::gen_mesh`
+        for vertices in [recursive_mesh(&object), table_mesh(&object)] {
+            for (a, b) in vertices.iter().zip(object.get_vertices()) {
+                assert!((a - b).norm() < 1e-3, "{label}: vértice {a:?} != {b:?}");
+            }
+        }
+
+        group.bench_function(BenchmarkId::new("recursiva", &label), |b| {
+            b.iter(|| recursive_mesh(black_box(&object)))
+        });
+        group.bench_function(BenchmarkId::new("tabela", &label), |b| {
+            b.iter(|| table_mesh(black_box(&object)))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_basis, bench_mesh);
+criterion_main!(benches);
@@ -0,0 +1,63 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use rayon::ThreadPoolBuilder;
+use aleluia::generator::Generator;
+use aleluia::object::Object;
+use aleluia::types::Vec3;
+use aleluia::utils::mat4x1_to_vec3;
+
+/// Vazão da geração da malha (vértices por segundo) com 1 thread, com as 4 threads fixas da
+/// implementação anterior e com todos os núcleos da máquina.
+fn bench_gen_mesh(c: &mut Criterion) {
+    let cores = std::thread::available_parallelism().map_or(1, |n| n.get());
+    let mut thread_counts = vec![1, 4, cores];
+    thread_counts.sort_unstable();
+    thread_counts.dedup();
+
+    let mut group = c.benchmark_group("gen_mesh");
+    group.sample_size(20);
+
+    for (n, t, res) in [(10, 3, 100), (20, 4, 300), (40, 4, 600)] {
+        let generator = Generator::Random { seed: 0, amplitude: 10.0 };
+        let mut object = Object::from_generator(&generator, n, n, t, t, res, res).unwrap();
+        let label = format!("n{n}_t{t}_res{res}");
+
+        group.throughput(Throughput::Elements((res * res) as u64));
+
+        for &threads in &thread_counts {
+            let pool = ThreadPoolBuilder::new().num_threads(threads).build().unwrap();
+            let id = BenchmarkId::new(format!("{threads}_threads"), &label);
+            group.bench_function(id, |b| b.iter(|| pool.install(|| object.gen_mesh())));
+        }
+    }
+
+    group.finish();
+}
+
+/// Malha inteira regenerada contra a atualização local após mover um único ponto de controle
+/// no meio da malha.
+fn bench_control_point(c: &mut Criterion) {
+    let mut group = c.benchmark_group("ponto_de_controle");
+    group.sample_size(20);
+
+    for (n, t, res) in [(20, 4, 300), (40, 4, 600)] {
+        let generator = Generator::Random { seed: 0, amplitude: 10.0 };
+        let mut object = Object::from_generator(&generator, n, n, t, t, res, res).unwrap();
+        let label = format!("n{n}_t{t}_res{res}");
+        let index = (n / 2) * (n + 1) + n / 2;
+
+        group.bench_function(BenchmarkId::new("gen_mesh", &label), |b| b.iter(|| object.gen_mesh()));
+        group.bench_function(BenchmarkId::new("set_control_point", &label), |b| {
+            // Apenas a altura do ponto muda, mantendo a sua posição na grade
+            let mut position: Vec3 = mat4x1_to_vec3(&object.control_points[index]);
+            b.iter(|| {
+                position.z += 0.01;
+                object.set_control_point(index, &position);
+            })
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_gen_mesh, bench_control_point);
+criterion_main!(benches);
@@ -0,0 +1,72 @@
+use eframe::egui::{ComboBox, DragValue, Ui};
+use aleluia::object::{KnotPreset, Object};
+
+/// Estado do editor de nós.
+pub struct KnotEditorData {
+    preset: KnotPreset,
+    /// Mensagem de erro da última alteração rejeitada.
+    error: Option<String>,
+}
+
+impl Default for KnotEditorData {
+    fn default() -> Self {
+        Self {
+            preset: KnotPreset::OpenUniform,
+            error: None,
+        }
+    }
+}
+
+/// Editor dos vetores de nós do objeto. Cada nó fica limitado pelos seus vizinhos, para manter
+/// o vetor não decrescente. Retorna `true` se os nós foram alterados.
+pub fn knot_editor(ui: &mut Ui, object: &mut Object, data: &mut KnotEditorData) -> bool {
+    let mut knots_i = object.get_knots_i().to_vec();
+    let mut knots_j = object.get_knots_j().to_vec();
+
+    let mut changed = false;
+    changed |= knot_inputs(ui, "Nós em i", &mut knots_i);
+    changed |= knot_inputs(ui, "Nós em j", &mut knots_j);
+
+    ui.horizontal(|ui| {
+        ComboBox::from_id_salt("knot_preset")
+            .selected_text(data.preset.name())
+            .show_ui(ui, |ui| {
+                for preset in KnotPreset::ALL {
+                    ui.selectable_value(&mut data.preset, preset, preset.name());
+                }
+            });
+
+        if ui.button("Aplicar").clicked() {
+            (knots_i, knots_j) = object.calc_knot_preset(data.preset);
+            changed = true;
+        }
+    });
+
+    if changed {
+        data.error = object.set_knots(knots_i, knots_j).err();
+    }
+
+    if let Some(error) = &data.error {
+        ui.colored_label(ui.visuals().error_fg_color, error);
+    }
+
+    changed && data.error.is_none()
+}
+
+fn knot_inputs(ui: &mut Ui, label: &str, knots: &mut [f32]) -> bool {
+    let mut changed = false;
+
+    ui.label(label);
+    ui.horizontal_wrapped(|ui| {
+        for k in 0..knots.len() {
+            let min = if k > 0 { knots[k - 1] } else { f32::MIN };
+            let max = knots.get(k + 1).copied().unwrap_or(f32::MAX);
+
+            changed |= ui
+                .add(DragValue::new(&mut knots[k]).speed(0.01).max_decimals(3).range(min..=max))
+                .changed();
+        }
+    });
+
+    changed
+}
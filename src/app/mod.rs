@@ -1,39 +1,136 @@
+pub mod knot_editor;
+pub mod new_surface;
 pub mod parse_input;
+pub mod scalar_input;
 pub mod vector_input;
+pub mod view;
+
+use std::path::PathBuf;
 
 use eframe::{App, Frame};
-use eframe::egui::{CentralPanel, Context, SidePanel, TopBottomPanel, Ui, Vec2, Sense, Shape, Rect, Pos2};
-use eframe::egui::emath::RectTransform;
+use eframe::egui::{menu, CentralPanel, DragValue, Context, SidePanel, TopBottomPanel, Ui, Vec2, Sense, Rect};
+use crate::app::knot_editor::{knot_editor, KnotEditorData};
+use crate::app::new_surface::{new_surface_dialog, NewSurfaceData};
+use crate::app::scalar_input::{scalar_input, ScalarInputData};
 use crate::app::vector_input::{vector_input, VectorInputData};
+use crate::app::view::View;
+use aleluia::camera::Camera;
+use aleluia::import::load_control_net;
+use aleluia::export::obj::save_obj;
+use aleluia::generator::Generator;
+use aleluia::export::stl::{save_stl, StlFormat};
 use crate::constants::GUI_SIDEBAR_WIDTH;
-use crate::object::Object;
-use crate::render::Render;
-use crate::types::*;
-use crate::utils::*;
+use aleluia::object::{DiagonalSplit, Object};
+use aleluia::render::{ProjectionType, Render, ShaderType, Window};
+use aleluia::scene::{ObjectData, Scene, ViewData};
+use aleluia::types::Vec3;
 
 pub struct MyApp {
     objects: Vec<Object>,
     selected_object: Option<usize>,
 
-    render: Render,
+    /// Vistas da cena (frente, topo, lado e perspectiva).
+    views: Vec<View>,
+    /// Vista editada pelos campos do painel lateral.
+    active_view: usize,
+    /// Vista que ocupa todo o painel central, se houver.
+    maximized_view: Option<usize>,
 
     vrp: VectorInputData,
     p: VectorInputData,
     y: VectorInputData,
+    dp: ScalarInputData,
+    near: ScalarInputData,
+    far: ScalarInputData,
+
+    xmin: ScalarInputData,
+    xmax: ScalarInputData,
+    ymin: ScalarInputData,
+    ymax: ScalarInputData,
+
+    /// Índices (i, j) do ponto de controle cujo peso é editado.
+    weight_point: [usize; 2],
+
+    /// Editor dos vetores de nós do objeto selecionado.
+    knot_editor: KnotEditorData,
+
+    /// Diálogo de criação de uma nova superfície.
+    new_surface: NewSurfaceData,
+
+    /// Caminho do arquivo de cena usado em Abrir e Salvar.
+    scene_path: String,
+    /// Caminho do arquivo usado na exportação das malhas. A extensão é definida pelo formato.
+    export_path: String,
+    /// Espessura do sólido exportado em STL, se a superfície for engrossada.
+    stl_thickness: Option<f32>,
+    /// Caminho do arquivo CSV ou OBJ com a malha de controle a importar.
+    import_path: String,
+    /// Ordens (ti, tj) da superfície importada.
+    import_orders: [usize; 2],
+    /// Resoluções (resi, resj) da superfície importada.
+    import_resolution: [usize; 2],
+    /// Colunas (nj + 1) da grade dos arquivos OBJ, se não forem deduzidas das faces.
+    import_columns: Option<usize>,
+    /// Mensagem de resultado da última operação com arquivos.
+    status: Option<String>,
 }
 
 impl Default for MyApp {
     fn default() -> Self {
-        Self {
-            objects: vec![Object::new(10, 10, 3, 3, 20, 20)],
+        let center = Vec3::new(5.0, 5.0, 5.0);
+
+        // A semente da superfície inicial é exibida para que ela possa ser recriada pelo
+        // diálogo "Nova superfície" ou pela opção --seed do subcomando render
+        let seed: u64 = rand::random();
+        let generator = Generator::Random { seed, amplitude: 10.0 };
+        let object = Object::from_generator(&generator, 10, 10, 3, 3, 20, 20)
+            .expect("Parâmetros válidos para a superfície inicial");
+
+        let mut app = Self {
+            objects: vec![object],
             selected_object: Some(0),
 
-            render: Render::default(),
+            views: vec![
+                View::front(center),
+                View::top(center),
+                View::side(center),
+                View::perspective(center),
+            ],
+            active_view: 3,
+            maximized_view: None,
 
-            vrp: VectorInputData::new(0.0, 0.0, 0.0),
+            vrp: VectorInputData::default(),
             p: VectorInputData::default(),
-            y: VectorInputData::new(0.0, 1.0, 0.0),
-        }
+            y: VectorInputData::default(),
+            dp: ScalarInputData::default(),
+            near: ScalarInputData::default(),
+            far: ScalarInputData::default(),
+
+            xmin: ScalarInputData::default(),
+            xmax: ScalarInputData::default(),
+            ymin: ScalarInputData::default(),
+            ymax: ScalarInputData::default(),
+
+            weight_point: [0, 0],
+
+            knot_editor: KnotEditorData::default(),
+
+            new_surface: NewSurfaceData::default(),
+
+            scene_path: "cena.json".to_string(),
+            export_path: "superficie".to_string(),
+            stl_thickness: None,
+            import_path: "malha.csv".to_string(),
+            import_orders: [3, 3],
+            import_resolution: [20, 20],
+            import_columns: None,
+            status: Some(format!("Semente da superfície inicial: {seed}")),
+        };
+
+        app.sync_camera_inputs();
+        app.sync_window_inputs();
+
+        app
     }
 }
 
@@ -55,90 +152,442 @@ impl App for MyApp {
             .show(ctx, |ui| {
                 self.central_panel_content(ui);
             });
+
+        if let Some(object) = new_surface_dialog(ctx, &mut self.new_surface) {
+            self.objects.push(object);
+            self.selected_object = Some(self.objects.len() - 1);
+        }
     }
 }
 
 impl MyApp {
     pub fn menu_bar_content(&mut self, ui: &mut Ui) {
-        ui.label("Menu bar");
+        menu::bar(ui, |ui| {
+            ui.menu_button("Arquivo", |ui| {
+                if ui.button("Nova superfície...").clicked() {
+                    self.new_surface.open = true;
+                    ui.close_menu();
+                }
+
+                ui.separator();
+
+                ui.horizontal(|ui| {
+                    ui.label("Arquivo:");
+                    ui.text_edit_singleline(&mut self.scene_path);
+                });
+
+                if ui.button("Abrir").clicked() {
+                    self.open_scene();
+                    ui.close_menu();
+                }
+                if ui.button("Salvar").clicked() {
+                    self.save_scene();
+                    ui.close_menu();
+                }
+
+                ui.separator();
+
+                ui.horizontal(|ui| {
+                    ui.label("Exportar:");
+                    ui.text_edit_singleline(&mut self.export_path);
+                });
+
+                if ui.button("Exportar OBJ").clicked() {
+                    self.export_obj();
+                    ui.close_menu();
+                }
+
+                ui.horizontal(|ui| {
+                    let mut thicken = self.stl_thickness.is_some();
+                    if ui.checkbox(&mut thicken, "Engrossar").changed() {
+                        self.stl_thickness = thicken.then_some(1.0);
+                    }
+                    if let Some(thickness) = &mut self.stl_thickness {
+                        ui.add(DragValue::new(thickness).speed(0.05).range(0.01..=100.0));
+                    }
+                });
+
+                if ui.button("Exportar STL (binário)").clicked() {
+                    self.export_stl(StlFormat::Binary);
+                    ui.close_menu();
+                }
+                if ui.button("Exportar STL (ASCII)").clicked() {
+                    self.export_stl(StlFormat::Ascii);
+                    ui.close_menu();
+                }
+
+                ui.separator();
+
+                ui.horizontal(|ui| {
+                    ui.label("Importar:");
+                    ui.text_edit_singleline(&mut self.import_path);
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Ordens:");
+                    ui.add(DragValue::new(&mut self.import_orders[0]).range(2..=10));
+                    ui.add(DragValue::new(&mut self.import_orders[1]).range(2..=10));
+                    ui.label("Resolução:");
+                    ui.add(DragValue::new(&mut self.import_resolution[0]).range(2..=200));
+                    ui.add(DragValue::new(&mut self.import_resolution[1]).range(2..=200));
+                });
+                ui.horizontal(|ui| {
+                    let mut columns = self.import_columns.is_some();
+                    if ui.checkbox(&mut columns, "Colunas (nj + 1) do OBJ").changed() {
+                        self.import_columns = columns.then_some(4);
+                    }
+                    if let Some(columns) = &mut self.import_columns {
+                        ui.add(DragValue::new(columns).range(2..=1000));
+                    }
+                });
+
+                if ui.button("Importar malha de controle").clicked() {
+                    self.import_control_net();
+                    ui.close_menu();
+                }
+            });
+
+            if let Some(status) = &self.status {
+                ui.label(status);
+            }
+        });
+    }
+
+    /// Carrega as superfícies e as vistas do arquivo de cena. As vistas são associadas pelo
+    /// nome; em caso de erro, a cena atual é mantida.
+    fn open_scene(&mut self) {
+        let path = PathBuf::from(&self.scene_path);
+
+        let result = Scene::load(&path)
+            .map_err(|error| error.to_string())
+            .and_then(|scene| {
+                let objects = scene.to_objects()?;
+
+                let mut renders: Vec<Render> = self.views.iter().map(|view| view.render.clone()).collect();
+                for view_data in &scene.views {
+                    if let Some(i) = self.views.iter().position(|view| view.name == view_data.name) {
+                        view_data.apply(&mut renders[i])?;
+                    }
+                }
+
+                Ok((objects, renders))
+            });
+
+        match result {
+            Ok((objects, renders)) => {
+                self.selected_object = if objects.is_empty() { None } else { Some(0) };
+                self.objects = objects;
+                for (view, render) in self.views.iter_mut().zip(renders) {
+                    view.render = render;
+                }
+
+                self.sync_camera_inputs();
+                self.sync_window_inputs();
+                self.status = Some(format!("Cena aberta: {}", path.display()));
+            }
+            Err(error) => {
+                self.status = Some(format!("Erro ao abrir {}: {error}", path.display()));
+            }
+        }
+    }
+
+    /// Salva as superfícies e as vistas no arquivo de cena.
+    fn save_scene(&mut self) {
+        let path = PathBuf::from(&self.scene_path);
+
+        let scene = Scene::new(
+            self.objects.iter().map(ObjectData::from_object).collect(),
+            self.views.iter().map(|view| ViewData::from_render(view.name, &view.render)).collect(),
+        );
+
+        self.status = Some(match scene.save(&path) {
+            Ok(()) => format!("Cena salva: {}", path.display()),
+            Err(error) => format!("Erro ao salvar {}: {error}", path.display()),
+        });
+    }
+
+    /// Exporta as malhas de todos os objetos para um arquivo OBJ.
+    fn export_obj(&mut self) {
+        let path = PathBuf::from(&self.export_path).with_extension("obj");
+
+        self.status = Some(match save_obj(&path, &self.objects) {
+            Ok(()) => format!("Malhas exportadas: {}", path.display()),
+            Err(error) => format!("Erro ao exportar {}: {error}", path.display()),
+        });
+    }
+
+    /// Exporta as malhas de todos os objetos para um arquivo STL, engrossando as superfícies
+    /// em sólidos fechados se a espessura estiver definida.
+    fn export_stl(&mut self, format: StlFormat) {
+        let path = PathBuf::from(&self.export_path).with_extension("stl");
+
+        self.status = Some(match save_stl(&path, &self.objects, format, self.stl_thickness) {
+            Ok(()) => format!("Malhas exportadas: {}", path.display()),
+            Err(error) => format!("Erro ao exportar {}: {error}", path.display()),
+        });
+    }
+
+    /// Cria uma nova superfície a partir da malha de controle de um arquivo CSV ou OBJ e a
+    /// seleciona.
+    fn import_control_net(&mut self) {
+        let path = PathBuf::from(&self.import_path);
+        let [ti, tj] = self.import_orders;
+        let [resi, resj] = self.import_resolution;
+
+        let result = load_control_net(&path, self.import_columns)
+            .map_err(|error| error.to_string())
+            .and_then(|control_net| control_net.to_object(ti, tj, resi, resj));
+
+        self.status = Some(match result {
+            Ok(object) => {
+                self.objects.push(object);
+                self.selected_object = Some(self.objects.len() - 1);
+                format!("Malha de controle importada: {}", path.display())
+            }
+            Err(error) => format!("Erro ao importar {}: {error}", path.display()),
+        });
     }
 
     pub fn side_panel_content(&mut self, ui: &mut Ui) {
         ui.label("Side panel");
 
+        ui.collapsing("Vista", |ui| {
+            let mut active_view = self.active_view;
+            for (i, view) in self.views.iter().enumerate() {
+                ui.radio_value(&mut active_view, i, view.name);
+            }
+
+            if active_view != self.active_view {
+                self.active_view = active_view;
+                self.sync_camera_inputs();
+                self.sync_window_inputs();
+            }
+        });
+
+        ui.collapsing("Pesos", |ui| {
+            self.weight_inputs(ui);
+        });
+
+        ui.collapsing("Nós", |ui| {
+            match self.selected_object.and_then(|i| self.objects.get_mut(i)) {
+                Some(object) => {
+                    knot_editor(ui, object, &mut self.knot_editor);
+                }
+                None => {
+                    ui.label("Nenhum objeto selecionado.");
+                }
+            }
+        });
+
+        ui.collapsing("Triangulação", |ui| {
+            match self.selected_object.and_then(|i| self.objects.get_mut(i)) {
+                Some(object) => {
+                    let mut triangulation = object.get_triangulation();
+                    ui.radio_value(&mut triangulation, None, "Nenhuma");
+                    for split in DiagonalSplit::ALL {
+                        ui.radio_value(&mut triangulation, Some(split), split.name());
+                    }
+
+                    if triangulation != object.get_triangulation() {
+                        object.set_triangulation(triangulation);
+                    }
+                }
+                None => {
+                    ui.label("Nenhum objeto selecionado.");
+                }
+            }
+        });
+
         ui.collapsing("Câmera", |ui| {
-            vector_input(ui, "VRP", &mut self.vrp);
-            vector_input(ui, "P", &mut self.p);
-            vector_input(ui, "Y", &mut self.y);
+            let mut applied = false;
+            applied |= vector_input(ui, "VRP", &mut self.vrp);
+            applied |= vector_input(ui, "P", &mut self.p);
+            applied |= vector_input(ui, "Y", &mut self.y);
+            applied |= scalar_input(ui, "DP", &mut self.dp);
+            applied |= scalar_input(ui, "Near", &mut self.near);
+            applied |= scalar_input(ui, "Far", &mut self.far);
+
+            if applied {
+                self.apply_camera_inputs();
+            }
+        });
+
+        ui.collapsing("Window", |ui| {
+            let mut applied = false;
+            applied |= scalar_input(ui, "Xmin", &mut self.xmin);
+            applied |= scalar_input(ui, "Xmax", &mut self.xmax);
+            applied |= scalar_input(ui, "Ymin", &mut self.ymin);
+            applied |= scalar_input(ui, "Ymax", &mut self.ymax);
+
+            if applied {
+                self.apply_window_inputs();
+            }
+        });
+
+        ui.collapsing("Projeção", |ui| {
+            let mut projection_type = self.render_mut().get_projection();
+            ui.radio_value(&mut projection_type, ProjectionType::Orthographic, "Ortográfica");
+            ui.radio_value(&mut projection_type, ProjectionType::Perspective, "Perspectiva");
+
+            if projection_type != self.render_mut().get_projection() {
+                self.render_mut().set_projection(projection_type);
+            }
+        });
+
+        ui.collapsing("Sombreamento", |ui| {
+            let mut shader_type = self.render_mut().get_shader();
+            ui.radio_value(&mut shader_type, ShaderType::Wireframe, "Aramado");
+            ui.radio_value(&mut shader_type, ShaderType::Constant, "Constante");
+            ui.radio_value(&mut shader_type, ShaderType::Gouraud, "Gouraud");
+            ui.radio_value(&mut shader_type, ShaderType::Phong, "Phong");
+
+            if shader_type != self.render_mut().get_shader() {
+                self.render_mut().set_shader(shader_type);
+            }
+
+            if shader_type == ShaderType::Wireframe {
+                let mut hidden_lines = self.render_mut().get_hidden_lines();
+                if ui.checkbox(&mut hidden_lines, "Remover linhas ocultas").changed() {
+                    self.render_mut().set_hidden_lines(hidden_lines);
+                }
+            }
         });
     }
 
-    pub fn central_panel_content(&mut self, ui: &mut Ui) {
-        let painter_size = Vec2::new(ui.available_width(), ui.available_height());
-        let painter_sense = Sense::hover();
-        let (
-            response,
-            painter,
-        ) = ui.allocate_painter(painter_size, painter_sense);
-
-        if let Some(selected_object) = self.selected_object {
-            let to_screen = RectTransform::from_to(
-                Rect::from_min_size(Pos2::ZERO, response.rect.size()),
-                response.rect,
-            );
+    /// Campos de edição do peso de um ponto de controle do objeto selecionado.
+    fn weight_inputs(&mut self, ui: &mut Ui) {
+        let Some(object) = self.selected_object.and_then(|i| self.objects.get_mut(i)) else {
+            ui.label("Nenhum objeto selecionado.");
+            return;
+        };
 
-            let control_point_radius = 8.0;
-            let control_point_shapes: Vec<Shape> = self.objects[selected_object].control_points
-                .iter_mut()
-                .enumerate()
-                .map(|(i, point_sru)| {
-                    let m_sru_srt: Mat4 = self.render.get_m_sru_srt();
+        let (ni, nj) = object.get_size();
+        let [i, j] = &mut self.weight_point;
+        *i = (*i).min(ni);
+        *j = (*j).min(nj);
 
-                    let mut point_srt: Mat4x1 = m_sru_srt * *point_sru;
-                    let mut point_srt_pos2 = Pos2::new(point_srt.x, point_srt.y);
+        ui.horizontal(|ui| {
+            ui.label("Ponto (i, j):");
+            ui.add(DragValue::new(i).range(0..=ni));
+            ui.add(DragValue::new(j).range(0..=nj));
+        });
 
-                    let size = Vec2::splat(2.0 * control_point_radius);
+        let index = *i * (nj + 1) + *j;
+        let mut weight = object.get_weight(index);
+        ui.horizontal(|ui| {
+            ui.label("Peso:");
+            let response = ui.add(DragValue::new(&mut weight).speed(0.01).range(0.01..=100.0));
+            if response.changed() {
+                // O intervalo do campo garante um peso válido
+                let _ = object.set_weight(index, weight);
+            }
+        });
+    }
 
-                    let point_in_screen = to_screen.transform_pos(point_srt_pos2);
-                    let point_rect = Rect::from_center_size(point_in_screen, size);
-                    let point_id = response.id.with(i);
-                    let point_response = ui.interact(point_rect, point_id, Sense::drag());
+    /// Atualiza os campos da câmera com os valores da câmera do render.
+    fn sync_camera_inputs(&mut self) {
+        let camera = self.render().get_camera().clone();
 
-                    let drag_delta = point_response.drag_delta();
+        self.vrp = VectorInputData::from_vec3(&camera.vrp);
+        self.p = VectorInputData::from_vec3(&camera.p);
+        self.y = VectorInputData::from_vec3(&camera.y);
+        self.dp = ScalarInputData::new(camera.dp);
+        self.near = ScalarInputData::new(camera.near);
+        self.far = ScalarInputData::new(camera.far);
+    }
 
-                    if drag_delta != Vec2::ZERO {
-                        let drag_delta_srt: Mat4x1 = Mat4x1::new(drag_delta.x, drag_delta.y, 0.0, 0.0);
-                        let drag_delta_sru: Mat4x1 = m_sru_srt.try_inverse().unwrap() * drag_delta_srt;
+    /// Atualiza os campos da window com os valores da window do render.
+    fn sync_window_inputs(&mut self) {
+        let window = self.render().get_window().clone();
 
-                        translate(&mut point_srt, drag_delta_srt.x, drag_delta_srt.y, drag_delta_srt.z);
-                        translate(&mut *point_sru, drag_delta_sru.x, drag_delta_sru.y, drag_delta_sru.z);
+        self.xmin = ScalarInputData::new(window.xmin);
+        self.xmax = ScalarInputData::new(window.xmax);
+        self.ymin = ScalarInputData::new(window.ymin);
+        self.ymax = ScalarInputData::new(window.ymax);
+    }
 
-                        point_srt_pos2 = Pos2::new(point_srt.x, point_srt.y);
+    /// Aplica os campos da câmera ao render. Se a câmera resultante for inválida, os campos
+    /// voltam aos valores atuais.
+    fn apply_camera_inputs(&mut self) {
+        let camera = Camera {
+            vrp: self.vrp.to_vec3(),
+            p: self.p.to_vec3(),
+            y: self.y.to_vec3(),
+            dp: self.dp.v,
+            near: self.near.v,
+            far: self.far.v,
+        };
 
-                        /*point_pos2 += point_response.drag_delta();
-                        point_pos2 = to_screen.from().clamp(point_pos2);
+        if camera.is_valid() {
+            self.render_mut().set_camera(camera);
+        }
+        self.sync_camera_inputs();
+    }
 
-                        point_sru.x = point_pos2.x;
-                        point_sru.y = point_pos2.y;
+    /// Aplica os campos da window ao render. Se a window resultante for inválida, os campos
+    /// voltam aos valores atuais.
+    fn apply_window_inputs(&mut self) {
+        let window = Window {
+            xmin: self.xmin.v,
+            xmax: self.xmax.v,
+            ymin: self.ymin.v,
+            ymax: self.ymax.v,
+        };
 
-                        *point_sru = m_sru_srt.try_inverse().unwrap() * *point_sru;*/
-                    }
+        if window.xmin < window.xmax && window.ymin < window.ymax {
+            self.render_mut().set_window(window);
+        }
+        self.sync_window_inputs();
+    }
 
-                    //point_srt_pos2 = to_screen.from().clamp(point_srt_pos2);
+    pub fn central_panel_content(&mut self, ui: &mut Ui) {
+        let painter_size = Vec2::new(ui.available_width(), ui.available_height());
+        let (rect, _) = ui.allocate_exact_size(painter_size, Sense::hover());
 
-                    let point_in_screen = Pos2::new(point_srt.x, point_srt.y);
-                    let stroke = ui.style().interact(&point_response).fg_stroke;
+        let panes: Vec<(usize, Rect)> = match self.maximized_view {
+            Some(i) => vec![(i, rect)],
+            None => {
+                let size = rect.size() / 2.0;
+                (0..self.views.len())
+                    .map(|i| {
+                        let offset = Vec2::new((i % 2) as f32 * size.x, (i / 2) as f32 * size.y);
+                        (i, Rect::from_min_size(rect.min + offset, size).shrink(1.0))
+                    })
+                    .collect()
+            }
+        };
 
-                    Shape::circle_stroke(point_in_screen, control_point_radius, stroke)
-                })
-                .collect();
+        for (i, pane) in panes {
+            let view_response = self.views[i].show(
+                ui,
+                pane,
+                &mut self.objects,
+                self.selected_object,
+                i == self.active_view,
+                self.maximized_view.is_some(),
+            );
 
-            /*let points_in_screen: Vec<Pos2> = control_points
-                .iter()
-                .map(|p| to_screen * *p)
-                .collect();*/
+            if view_response.toggle_maximize {
+                self.maximized_view = match self.maximized_view {
+                    Some(_) => None,
+                    None => Some(i),
+                };
+            }
 
-            painter.extend(control_point_shapes);
+            if view_response.interacted || (view_response.camera_changed && i == self.active_view) {
+                self.active_view = i;
+                self.sync_camera_inputs();
+                self.sync_window_inputs();
+            }
         }
     }
-}
\ No newline at end of file
+
+    /// Render da vista ativa.
+    fn render(&self) -> &Render {
+        &self.views[self.active_view].render
+    }
+
+    /// Render da vista ativa, para alteração pelos campos do painel lateral.
+    fn render_mut(&mut self) -> &mut Render {
+        &mut self.views[self.active_view].render
+    }
+}
@@ -0,0 +1,211 @@
+use std::mem::discriminant;
+use std::ops::RangeInclusive;
+
+use eframe::egui::{ComboBox, Context, DragValue, Grid, Ui, Window};
+use aleluia::conic::Conic;
+use aleluia::generator::Generator;
+use aleluia::object::Object;
+
+/// Geradores disponíveis no diálogo, com os seus parâmetros iniciais.
+const GENERATORS: [Generator; 5] = [
+    Generator::Random { seed: 0, amplitude: 10.0 },
+    Generator::Plane { height: 5.0 },
+    Generator::Noise { seed: 0, amplitude: 10.0, frequency: 0.2, octaves: 4 },
+    Generator::Sine { amplitude: 4.0, waves_i: 1.0, waves_j: 1.0 },
+    Generator::Paraboloid { amplitude: 10.0 },
+];
+
+/// Superfícies cônicas disponíveis no diálogo, com os seus parâmetros iniciais.
+const CONICS: [Conic; 3] = [
+    Conic::Cylinder { radius: 3.0, height: 6.0 },
+    Conic::Sphere { radius: 4.0 },
+    Conic::Torus { major_radius: 5.0, minor_radius: 1.5 },
+];
+
+/// Estado do diálogo "Nova superfície".
+pub struct NewSurfaceData {
+    pub open: bool,
+    /// Cônica exata a criar. Se `None`, a superfície é criada pelo gerador de alturas.
+    conic: Option<Conic>,
+    generator: Generator,
+    /// Quantidades de pontos de controle (ni, nj), contadas a partir de zero.
+    size: [usize; 2],
+    /// Ordens (ti, tj).
+    orders: [usize; 2],
+    /// Resoluções (resi, resj).
+    resolution: [usize; 2],
+    /// Mensagem de erro da última tentativa de criação.
+    error: Option<String>,
+}
+
+impl Default for NewSurfaceData {
+    fn default() -> Self {
+        Self {
+            open: false,
+            conic: None,
+            generator: GENERATORS[0],
+            size: [10, 10],
+            orders: [3, 3],
+            resolution: [20, 20],
+            error: None,
+        }
+    }
+}
+
+/// Mostra o diálogo "Nova superfície". Retorna a superfície criada, se o usuário confirmar.
+pub fn new_surface_dialog(ctx: &Context, data: &mut NewSurfaceData) -> Option<Object> {
+    let mut open = data.open;
+    let mut created: Option<Object> = None;
+
+    Window::new("Nova superfície")
+        .open(&mut open)
+        .resizable(false)
+        .collapsible(false)
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                if ui.radio(data.conic.is_none(), "Grade de alturas").clicked() {
+                    data.conic = None;
+                }
+                if ui.radio(data.conic.is_some(), "Cônica exata").clicked() && data.conic.is_none() {
+                    data.conic = Some(CONICS[0]);
+                }
+            });
+
+            match &mut data.conic {
+                None => {
+                    ComboBox::from_label("Gerador")
+                        .selected_text(data.generator.name())
+                        .show_ui(ui, |ui| {
+                            for generator in GENERATORS {
+                                let selected = discriminant(&generator) == discriminant(&data.generator);
+                                if ui.selectable_label(selected, generator.name()).clicked() && !selected {
+                                    data.generator = generator;
+                                }
+                            }
+                        });
+                }
+                Some(conic) => {
+                    ComboBox::from_label("Superfície")
+                        .selected_text(conic.name())
+                        .show_ui(ui, |ui| {
+                            for preset in CONICS {
+                                let selected = discriminant(&preset) == discriminant(conic);
+                                if ui.selectable_label(selected, preset.name()).clicked() && !selected {
+                                    *conic = preset;
+                                }
+                            }
+                        });
+                }
+            }
+
+            Grid::new("new_surface_grid").num_columns(2).show(ui, |ui| {
+                match &mut data.conic {
+                    Some(conic) => conic_inputs(ui, conic),
+                    None => {
+                        generator_inputs(ui, &mut data.generator);
+                        pair_row(ui, "Pontos de controle", &mut data.size, 1..=100);
+                        pair_row(ui, "Ordens", &mut data.orders, 2..=10);
+                    }
+                }
+                pair_row(ui, "Resolução", &mut data.resolution, 2..=200);
+            });
+
+            if let Some(error) = &data.error {
+                ui.colored_label(ui.visuals().error_fg_color, error);
+            }
+
+            if ui.button("Criar").clicked() {
+                let [ni, nj] = data.size;
+                let [ti, tj] = data.orders;
+                let [resi, resj] = data.resolution;
+
+                let result = match data.conic {
+                    Some(conic) => conic.to_object(resi, resj),
+                    None => Object::from_generator(&data.generator, ni, nj, ti, tj, resi, resj),
+                };
+
+                match result {
+                    Ok(object) => {
+                        data.error = None;
+                        created = Some(object);
+                    }
+                    Err(error) => data.error = Some(error),
+                }
+            }
+        });
+
+    data.open = open && created.is_none();
+    created
+}
+
+/// Campos dos parâmetros do gerador, uma linha da grade por parâmetro.
+fn generator_inputs(ui: &mut Ui, generator: &mut Generator) {
+    match generator {
+        Generator::Random { seed, amplitude } => {
+            seed_input(ui, seed);
+            scalar_row(ui, "Amplitude", amplitude);
+        }
+        Generator::Plane { height } => {
+            scalar_row(ui, "Altura", height);
+        }
+        Generator::Noise { seed, amplitude, frequency, octaves } => {
+            seed_input(ui, seed);
+            scalar_row(ui, "Amplitude", amplitude);
+            scalar_row(ui, "Frequência", frequency);
+            ui.label("Oitavas");
+            ui.add(DragValue::new(octaves).range(1..=8));
+            ui.end_row();
+        }
+        Generator::Sine { amplitude, waves_i, waves_j } => {
+            scalar_row(ui, "Amplitude", amplitude);
+            scalar_row(ui, "Ondas em i", waves_i);
+            scalar_row(ui, "Ondas em j", waves_j);
+        }
+        Generator::Paraboloid { amplitude } => {
+            scalar_row(ui, "Amplitude", amplitude);
+        }
+    }
+}
+
+/// Campos dos parâmetros da cônica, uma linha da grade por parâmetro.
+fn conic_inputs(ui: &mut Ui, conic: &mut Conic) {
+    match conic {
+        Conic::Cylinder { radius, height } => {
+            scalar_row(ui, "Raio", radius);
+            scalar_row(ui, "Altura", height);
+        }
+        Conic::Sphere { radius } => {
+            scalar_row(ui, "Raio", radius);
+        }
+        Conic::Torus { major_radius, minor_radius } => {
+            scalar_row(ui, "Raio maior", major_radius);
+            scalar_row(ui, "Raio menor", minor_radius);
+        }
+    }
+}
+
+fn seed_input(ui: &mut Ui, seed: &mut u64) {
+    ui.label("Semente");
+    ui.horizontal(|ui| {
+        ui.add(DragValue::new(seed));
+        if ui.button("Sortear").clicked() {
+            *seed = rand::random();
+        }
+    });
+    ui.end_row();
+}
+
+fn pair_row(ui: &mut Ui, label: &str, values: &mut [usize; 2], range: RangeInclusive<usize>) {
+    ui.label(label);
+    ui.horizontal(|ui| {
+        ui.add(DragValue::new(&mut values[0]).range(range.clone()));
+        ui.add(DragValue::new(&mut values[1]).range(range));
+    });
+    ui.end_row();
+}
+
+fn scalar_row(ui: &mut Ui, label: &str, value: &mut f32) {
+    ui.label(label);
+    ui.add(DragValue::new(value).speed(0.05));
+    ui.end_row();
+}
@@ -1,18 +1,21 @@
-pub fn parse_input(prefix: &str, value: &mut f32, string: &mut String) {
+/// Lê um valor numérico da string, opcionalmente precedido pelo prefixo.
+/// Retorna `true` se o valor foi lido com sucesso.
+pub fn parse_input(prefix: &str, value: &mut f32, string: &mut String) -> bool {
     if string.starts_with(prefix) {
         if let Some(num_str) = string.strip_prefix(prefix) {
             if let Ok(parsed_value) = num_str.trim().parse::<f32>() {
                 *value = parsed_value;
                 *string = format!("{prefix} {parsed_value}");
-                return;
+                return true;
             }
         }
     } else {
         if let Ok(parsed_value) = string.trim().parse::<f32>() {
             *value = parsed_value;
             *string = format!("{prefix} {parsed_value}");
-            return;
+            return true;
         }
     }
-    *string = format!("Inválido!");
+    *string = "Inválido!".to_string();
+    false
 }
\ No newline at end of file
@@ -0,0 +1,46 @@
+use eframe::egui::{TextEdit, Ui};
+use crate::constants::GUI_VECTOR_INPUT_WIDTH;
+use crate::app::parse_input::parse_input;
+
+pub struct ScalarInputData {
+    pub v: f32,
+    pub s: String,
+}
+
+impl Default for ScalarInputData {
+    fn default() -> Self {
+        Self {
+            v: 0.0,
+            s: "0".to_string(),
+        }
+    }
+}
+
+impl ScalarInputData {
+    pub fn new(v: f32) -> Self {
+        Self {
+            v,
+            s: format!("{}", v),
+        }
+    }
+}
+
+/// Campo de entrada de um valor escalar. Retorna `true` quando um novo valor válido é aplicado.
+pub fn scalar_input(ui: &mut Ui, label: &str, data: &mut ScalarInputData) -> bool {
+    let mut applied = false;
+
+    ui.horizontal(|ui| {
+        ui.label(label);
+        ui.add(TextEdit::singleline(&mut data.s)
+            .desired_width(GUI_VECTOR_INPUT_WIDTH));
+
+        if ui.button("Aplicar").clicked() {
+            applied = parse_input("", &mut data.v, &mut data.s);
+            if applied {
+                data.s = format!("{}", data.v);
+            }
+        }
+    });
+
+    applied
+}
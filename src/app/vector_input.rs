@@ -1,6 +1,7 @@
 use eframe::egui::{TextEdit, Ui};
 use crate::constants::GUI_VECTOR_INPUT_WIDTH;
 use crate::app::parse_input::parse_input;
+use aleluia::types::Vec3;
 
 pub struct VectorInputData {
     pub xv: f32,
@@ -25,6 +26,14 @@ impl Default for VectorInputData {
 }
 
 impl VectorInputData {
+    pub fn from_vec3(vec3: &Vec3) -> Self {
+        Self::new(vec3[0], vec3[1], vec3[2])
+    }
+
+    pub fn to_vec3(&self) -> Vec3 {
+        Vec3::new(self.xv, self.yv, self.zv)
+    }
+
     pub fn new(xv: f32, yv: f32, zv: f32) -> Self {
         Self {
             xv,
@@ -37,7 +46,10 @@ impl VectorInputData {
     }
 }
 
-pub fn vector_input(ui: &mut Ui, label: &str, data: &mut VectorInputData) {
+/// Campo de entrada de um vetor. Retorna `true` quando um novo vetor válido é aplicado.
+pub fn vector_input(ui: &mut Ui, label: &str, data: &mut VectorInputData) -> bool {
+    let mut applied = false;
+
     ui.collapsing(label, |ui| {
         ui.horizontal(|ui| {
             ui.add(TextEdit::singleline(&mut data.xs)
@@ -48,10 +60,13 @@ pub fn vector_input(ui: &mut Ui, label: &str, data: &mut VectorInputData) {
                 .desired_width(GUI_VECTOR_INPUT_WIDTH));
 
             if ui.button("Aplicar").clicked() {
-                parse_input("X:", &mut data.xv, &mut data.xs);
-                parse_input("Y:", &mut data.yv, &mut data.ys);
-                parse_input("Z:", &mut data.zv, &mut data.zs);
+                let x = parse_input("X:", &mut data.xv, &mut data.xs);
+                let y = parse_input("Y:", &mut data.yv, &mut data.ys);
+                let z = parse_input("Z:", &mut data.zv, &mut data.zs);
+                applied = x && y && z;
             }
         });
     });
+
+    applied
 }
@@ -0,0 +1,288 @@
+use eframe::egui::{Align2, Button, Color32, ColorImage, FontId, Id, Painter, PointerButton, Pos2, Rect, Response, Sense, Shape, Stroke, TextureHandle, TextureOptions, Ui, Vec2};
+use eframe::egui::emath::RectTransform;
+use aleluia::camera::Camera;
+use crate::constants::{GUI_ORBIT_SPEED, GUI_ZOOM_SPEED};
+use aleluia::object::Object;
+use aleluia::render::{ProjectionType, Render, ShaderType, Viewport, Window};
+use aleluia::render::frame_buffer::FrameBuffer;
+use aleluia::types::*;
+use aleluia::utils::*;
+
+/// Resultado das interações do usuário com uma vista em um quadro.
+#[derive(Default)]
+pub struct ViewResponse {
+    /// O usuário interagiu com a vista (clique, arraste ou rolagem).
+    pub interacted: bool,
+    /// A câmera ou a window da vista foram alteradas.
+    pub camera_changed: bool,
+    /// O botão de maximizar/restaurar foi clicado.
+    pub toggle_maximize: bool,
+}
+
+/// Vista da cena, com seu próprio render e buffer de quadro.
+pub struct View {
+    pub name: &'static str,
+    pub render: Render,
+    frame_buffer: FrameBuffer,
+    texture: Option<TextureHandle>,
+}
+
+impl View {
+    pub fn new(name: &'static str, render: Render) -> Self {
+        let frame_buffer = FrameBuffer::from_viewport(render.get_viewport());
+
+        Self {
+            name,
+            render,
+            frame_buffer,
+            texture: None,
+        }
+    }
+
+    /// Cria uma vista paralela olhando para `p` a partir da direção `direction`.
+    fn orthographic(name: &'static str, p: Vec3, direction: Vec3, y: Vec3) -> Self {
+        let mut render = Render::default();
+        render.set_camera(Camera {
+            vrp: p + direction * 50.0,
+            p,
+            y,
+            dp: 40.0,
+            near: 1.0,
+            far: 200.0,
+        });
+        render.set_window(Window {
+            xmin: -10.0,
+            xmax: 10.0,
+            ymin: -10.0,
+            ymax: 10.0,
+        });
+        render.set_projection(ProjectionType::Orthographic);
+        render.set_shader(ShaderType::Wireframe);
+
+        Self::new(name, render)
+    }
+
+    /// Vista frontal, olhando na direção -z.
+    pub fn front(p: Vec3) -> Self {
+        Self::orthographic("Frente", p, Vec3::new(0.0, 0.0, 1.0), Vec3::new(0.0, 1.0, 0.0))
+    }
+
+    /// Vista de topo, olhando na direção -y.
+    pub fn top(p: Vec3) -> Self {
+        Self::orthographic("Topo", p, Vec3::new(0.0, 1.0, 0.0), Vec3::new(0.0, 0.0, -1.0))
+    }
+
+    /// Vista lateral, olhando na direção -x.
+    pub fn side(p: Vec3) -> Self {
+        Self::orthographic("Lado", p, Vec3::new(1.0, 0.0, 0.0), Vec3::new(0.0, 1.0, 0.0))
+    }
+
+    /// Vista em perspectiva, olhando para `p` de cima, da frente e da direita.
+    pub fn perspective(p: Vec3) -> Self {
+        let mut render = Render::default();
+        let mut camera = render.get_camera().clone();
+        camera.vrp = p + Vec3::new(20.0, 15.0, 35.0);
+        camera.p = p;
+        render.set_camera(camera);
+        render.set_projection(ProjectionType::Perspective);
+        render.set_shader(ShaderType::Gouraud);
+
+        Self::new("Perspectiva", render)
+    }
+
+    /// Desenha a vista no retângulo e trata as interações com a câmera e com os pontos de
+    /// controle do objeto selecionado.
+    pub fn show(
+        &mut self,
+        ui: &mut Ui,
+        rect: Rect,
+        objects: &mut [Object],
+        selected_object: Option<usize>,
+        active: bool,
+        maximized: bool,
+    ) -> ViewResponse {
+        let mut view_response = ViewResponse::default();
+
+        let id = Id::new("view").with(self.name);
+        let response = ui.interact(rect, id, Sense::click_and_drag());
+        let painter = ui.painter_at(rect);
+
+        let image_rect = self.fit_viewport(rect);
+
+        view_response.camera_changed = self.camera_controls(ui, &response);
+        view_response.interacted = view_response.camera_changed
+            || response.clicked()
+            || response.drag_started();
+
+        self.render_frame_buffer(objects);
+        self.paint_frame_buffer(ui, &painter, image_rect);
+
+        if let Some(selected_object) = selected_object {
+            let to_screen = RectTransform::from_to(
+                Rect::from_min_size(Pos2::ZERO, image_rect.size()),
+                image_rect,
+            );
+            let viewport = self.render.get_viewport();
+            let origin = Vec2::new(viewport.umin, viewport.vmin);
+            let m_sru_srt: Mat4 = self.render.get_m_sru_srt();
+            let object = &mut objects[selected_object];
+
+            let control_point_radius = 8.0;
+            let mut control_point_shapes: Vec<Shape> = Vec::with_capacity(object.control_points.len());
+            let mut moved: Option<(usize, Vec3)> = None;
+
+            for (i, point_sru) in object.control_points.iter().enumerate() {
+                let point_srt: Mat4x1 = m_sru_srt * *point_sru;
+
+                // Pontos atrás do centro de projeção não são desenhados
+                if point_srt.w <= 0.0 {
+                    continue;
+                }
+
+                let point_srt_pos2 = Pos2::new(point_srt.x / point_srt.w, point_srt.y / point_srt.w) - origin;
+                let size = Vec2::splat(2.0 * control_point_radius);
+
+                let mut point_in_screen = to_screen.transform_pos(point_srt_pos2);
+                let point_rect = Rect::from_center_size(point_in_screen, size);
+                let point_id = id.with(i);
+                let point_response = ui.interact(point_rect, point_id, Sense::drag());
+
+                let drag_delta = point_response.drag_delta();
+
+                if drag_delta != Vec2::ZERO {
+                    let position: Vec3 = mat4x1_to_vec3(point_sru);
+                    let drag_delta_sru: Vec3 = self.render.calc_drag_delta(
+                        &position,
+                        drag_delta.x,
+                        drag_delta.y,
+                    );
+
+                    point_in_screen += drag_delta;
+                    moved = Some((i, position + drag_delta_sru));
+                }
+
+                if point_response.drag_started() {
+                    view_response.interacted = true;
+                }
+
+                let stroke = ui.style().interact(&point_response).fg_stroke;
+                control_point_shapes.push(Shape::circle_stroke(point_in_screen, control_point_radius, stroke));
+            }
+
+            // Apenas a região da malha no suporte local do ponto movido é recalculada
+            if let Some((index, position)) = moved {
+                object.set_control_point(index, &position);
+            }
+
+            painter.extend(control_point_shapes);
+        }
+
+        // Identificação da vista e borda de destaque da vista ativa
+        painter.text(
+            rect.left_top() + Vec2::new(6.0, 4.0),
+            Align2::LEFT_TOP,
+            self.name,
+            FontId::proportional(14.0),
+            Color32::LIGHT_GRAY,
+        );
+        let stroke = if active {
+            Stroke::new(2.0, ui.visuals().selection.stroke.color)
+        } else {
+            Stroke::new(1.0, Color32::DARK_GRAY)
+        };
+        painter.rect_stroke(rect, 0.0, stroke);
+
+        let button_text = if maximized { "Restaurar" } else { "Maximizar" };
+        let button_rect = Rect::from_min_size(rect.right_top() + Vec2::new(-80.0, 4.0), Vec2::new(76.0, 18.0));
+        if ui.put(button_rect, Button::new(button_text).small()).clicked() {
+            view_response.toggle_maximize = true;
+            view_response.interacted = true;
+        }
+
+        view_response
+    }
+
+    /// Ajusta a viewport do render ao maior retângulo, centralizado no quadro, com a mesma
+    /// proporção da window. Retorna o retângulo da imagem na tela.
+    fn fit_viewport(&mut self, rect: Rect) -> Rect {
+        let window = self.render.get_window();
+        let aspect = (window.xmax - window.xmin) / (window.ymax - window.ymin);
+
+        let mut size = rect.size();
+        if size.x / size.y > aspect {
+            size.x = size.y * aspect;
+        } else {
+            size.y = size.x / aspect;
+        }
+        let size = size.floor().max(Vec2::splat(2.0));
+
+        let viewport = self.render.get_viewport();
+        if viewport.umax - viewport.umin != size.x - 1.0 || viewport.vmax - viewport.vmin != size.y - 1.0 {
+            self.render.set_viewport(Viewport {
+                umin: 0.0,
+                umax: size.x - 1.0,
+                vmin: 0.0,
+                vmax: size.y - 1.0,
+            });
+        }
+
+        Rect::from_center_size(rect.center(), size)
+    }
+
+    /// Controla a câmera com o mouse: arrastar com o botão principal orbita ao redor de P,
+    /// arrastar com o botão secundário, o do meio ou com Shift translada a câmera e a roda
+    /// do mouse aproxima ou afasta. Retorna `true` se a câmera foi alterada.
+    fn camera_controls(&mut self, ui: &Ui, response: &Response) -> bool {
+        let mut changed = false;
+        let delta = response.drag_delta();
+        let shift = ui.input(|i| i.modifiers.shift);
+
+        if delta != Vec2::ZERO {
+            if response.dragged_by(PointerButton::Secondary)
+                || response.dragged_by(PointerButton::Middle)
+                || (response.dragged_by(PointerButton::Primary) && shift)
+            {
+                self.render.pan(delta.x, delta.y);
+                changed = true;
+            } else if response.dragged_by(PointerButton::Primary) {
+                self.render.orbit(-delta.x * GUI_ORBIT_SPEED, -delta.y * GUI_ORBIT_SPEED);
+                changed = true;
+            }
+        }
+
+        if response.hovered() {
+            let scroll = ui.input(|i| i.smooth_scroll_delta.y);
+            if scroll != 0.0 {
+                self.render.zoom((-scroll * GUI_ZOOM_SPEED).exp());
+                changed = true;
+            }
+        }
+
+        changed
+    }
+
+    /// Rasteriza todos os objetos no buffer de quadro.
+    fn render_frame_buffer(&mut self, objects: &[Object]) {
+        self.frame_buffer.resize_to_viewport(self.render.get_viewport());
+        self.render.render_objects(&mut self.frame_buffer, objects);
+    }
+
+    /// Envia o buffer de quadro para uma textura e a desenha no retângulo.
+    fn paint_frame_buffer(&mut self, ui: &Ui, painter: &Painter, rect: Rect) {
+        let size = [self.frame_buffer.width(), self.frame_buffer.height()];
+        let image = ColorImage::from_rgb(size, self.frame_buffer.get_color());
+
+        let texture = match &mut self.texture {
+            Some(texture) => {
+                texture.set(image, TextureOptions::NEAREST);
+                texture
+            }
+            None => self.texture.insert(
+                ui.ctx().load_texture(self.name, image, TextureOptions::NEAREST),
+            ),
+        };
+
+        let uv = Rect::from_min_max(Pos2::ZERO, Pos2::new(1.0, 1.0));
+        painter.image(texture.id(), rect, uv, Color32::WHITE);
+    }
+}
@@ -0,0 +1,157 @@
+use std::ops::Range;
+
+/// Encontra o intervalo de nós `[u(k), u(k + 1))` não vazio que contém `v`, com
+/// `t - 1 <= k <= n`. Parâmetros fora do domínio `[u(t - 1), u(n + 1)]` são limitados a ele, e
+/// o fim do domínio pertence ao último intervalo não vazio.
+pub fn find_span(knots: &[f32], n: usize, t: usize, v: f32) -> usize {
+    let end = knots[n + 1];
+    let v = v.clamp(knots[t - 1], end);
+
+    if v >= end {
+        knots.partition_point(|&knot| knot < end) - 1
+    } else {
+        knots.partition_point(|&knot| knot <= v) - 1
+    }
+}
+
+/// Calcula as `t` funções de base não nulas no intervalo `span` pelo algoritmo de Cox–de Boor
+/// em tabela triangular, sem recursão e sem alocações. `basis[a]` é o valor da função do ponto
+/// de controle `span - t + 1 + a`.
+pub fn basis_functions(knots: &[f32], span: usize, t: usize, v: f32, basis: &mut [f32]) {
+    // Distâncias de v aos nós à esquerda e à direita do intervalo
+    let left = |j: usize| v - knots[span + 1 - j];
+    let right = |j: usize| knots[span + j] - v;
+
+    basis[0] = 1.0;
+    for j in 1..t {
+        let mut saved = 0.0;
+        for (r, value) in basis[..j].iter_mut().enumerate() {
+            let temp = *value / (right(r + 1) + left(j - r));
+            *value = saved + right(r + 1) * temp;
+            saved = left(j - r) * temp;
+        }
+        basis[j] = saved;
+    }
+}
+
+/// Tabela das funções de base não nulas em uma sequência de parâmetros, calculada uma única vez
+/// por linha ou coluna da malha.
+#[derive(Clone, Debug)]
+pub struct BasisTable {
+    order: usize,
+    /// Índice do primeiro ponto de controle com função de base não nula em cada parâmetro.
+    first: Vec<usize>,
+    /// `order` valores por parâmetro.
+    values: Vec<f32>,
+}
+
+impl BasisTable {
+    pub fn new(knots: &[f32], n: usize, t: usize, parameters: &[f32]) -> Self {
+        let mut first = Vec::with_capacity(parameters.len());
+        let mut values = vec![0.0; parameters.len() * t];
+
+        for (k, &v) in parameters.iter().enumerate() {
+            let span = find_span(knots, n, t, v);
+            basis_functions(knots, span, t, v, &mut values[k * t..(k + 1) * t]);
+            first.push(span + 1 - t);
+        }
+
+        Self {
+            order: t,
+            first,
+            values,
+        }
+    }
+
+    /// Retorna o índice do primeiro ponto de controle e os valores das funções de base não
+    /// nulas no `k`-ésimo parâmetro.
+    #[inline(always)]
+    pub fn get(&self, k: usize) -> (usize, &[f32]) {
+        (self.first[k], &self.values[k * self.order..(k + 1) * self.order])
+    }
+
+    /// Intervalo dos parâmetros em que a função de base do ponto de controle `point` pode ser
+    /// não nula (suporte local). Como `first` é não decrescente, o intervalo é contíguo.
+    pub fn support(&self, point: usize) -> Range<usize> {
+        let start = self.first.partition_point(|&first| first + self.order <= point);
+        let end = self.first.partition_point(|&first| first <= point);
+        start..end
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Função de base pela definição recursiva de Cox–de Boor.
+    fn recursive_basis(k: usize, t: usize, u: &[f32], v: f32) -> f32 {
+        if t == 1 {
+            return if u[k] <= v && v < u[k + 1] { 1.0 } else { 0.0 };
+        }
+
+        let mut value = 0.0;
+        if u[k + t - 1] != u[k] {
+            value += (v - u[k]) / (u[k + t - 1] - u[k]) * recursive_basis(k, t - 1, u, v);
+        }
+        if u[k + t] != u[k + 1] {
+            value += (u[k + t] - v) / (u[k + t] - u[k + 1]) * recursive_basis(k + 1, t - 1, u, v);
+        }
+        value
+    }
+
+    /// Vetores de nós (n, t, nós): uniforme aberto, periódico, não uniforme e com nós internos
+    /// repetidos.
+    fn knot_vectors() -> Vec<(usize, usize, Vec<f32>)> {
+        vec![
+            (5, 3, vec![0.0, 0.0, 0.0, 1.0, 2.0, 3.0, 4.0, 4.0, 4.0]),
+            (5, 4, vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0]),
+            (6, 4, vec![0.0, 0.0, 0.0, 0.0, 0.3, 0.5, 1.7, 2.0, 2.0, 2.0, 2.0]),
+            (8, 3, vec![0.0, 0.0, 0.0, 1.0, 1.0, 2.0, 2.0, 3.0, 3.0, 4.0, 4.0, 4.0]),
+            (6, 4, vec![0.0, 0.0, 0.0, 0.0, 1.0, 1.0, 1.0, 2.0, 2.0, 2.0, 2.0]),
+        ]
+    }
+
+    /// Parâmetros no domínio `[u(t - 1), u(n + 1))`, incluindo os nós internos.
+    fn parameters(knots: &[f32], n: usize, t: usize) -> Vec<f32> {
+        let (start, end) = (knots[t - 1], knots[n + 1]);
+        (0..64).map(|k| start + (end - start) * k as f32 / 64.0).collect()
+    }
+
+    #[test]
+    fn table_matches_recursive_basis() {
+        for (n, t, knots) in knot_vectors() {
+            let parameters = parameters(&knots, n, t);
+            let table = BasisTable::new(&knots, n, t, &parameters);
+
+            for (p, &v) in parameters.iter().enumerate() {
+                let (first, values) = table.get(p);
+                for k in 0..=n {
+                    let expected = recursive_basis(k, t, &knots, v);
+                    let actual = if (first..first + t).contains(&k) { values[k - first] } else { 0.0 };
+                    assert!(
+                        (actual - expected).abs() < 1e-5,
+                        "nós {knots:?}, v = {v}, N({k}, {t}) = {actual} != {expected}",
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn partition_of_unity() {
+        for (n, t, knots) in knot_vectors() {
+            // Inclui o fim do domínio, que pertence ao último intervalo não vazio
+            let mut parameters = parameters(&knots, n, t);
+            parameters.push(knots[n + 1]);
+            let table = BasisTable::new(&knots, n, t, &parameters);
+
+            for (p, &v) in parameters.iter().enumerate() {
+                let (first, values) = table.get(p);
+                let sum: f32 = values.iter().sum();
+                assert!((sum - 1.0).abs() < 1e-5, "nós {knots:?}, v = {v}: soma {sum}");
+                assert!(values.iter().all(|&value| value >= -1e-6));
+                assert!(first + t <= n + 1);
+            }
+        }
+    }
+}
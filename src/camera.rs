@@ -1,2 +1,75 @@
+use nalgebra::{Rotation3, Unit};
+use serde::{Deserialize, Serialize};
 use crate::types::Vec3;
 
+/// Menor ângulo (seno) permitido entre a direção de visão e o vetor Y ao orbitar.
+const MIN_ORBIT_SIN: f32 = 0.05;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Camera {
+    pub vrp: Vec3,
+    pub p: Vec3,
+    pub y: Vec3,
+    pub dp: f32,
+    /// Distância do plano de recorte próximo ao VRP.
+    pub near: f32,
+    /// Distância do plano de recorte distante ao VRP.
+    pub far: f32,
+}
+
+impl Camera {
+    /// Verifica se a câmera define um sistema de coordenadas e um volume de visão válidos.
+    pub fn is_valid(&self) -> bool {
+        let n: Vec3 = self.vrp - self.p;
+
+        n.norm() > f32::EPSILON
+            && self.y.cross(&n).norm() > f32::EPSILON
+            && self.dp > 0.0
+            && self.near > 0.0
+            && self.near < self.far
+    }
+
+    /// Retorna a base ortonormal (u, v, n) do SRC.
+    pub fn basis(&self) -> (Vec3, Vec3, Vec3) {
+        let nn: Vec3 = (self.vrp - self.p).normalize();
+        let vn: Vec3 = (self.y - self.y.dot(&nn) * nn).normalize();
+        let un: Vec3 = vn.cross(&nn);
+        (un, vn, nn)
+    }
+
+    /// Gira o VRP ao redor de P. `yaw` gira em torno de Y e `pitch` em torno do eixo u do SRC,
+    /// ambos em radianos. O `pitch` é ignorado se levar a direção de visão a ficar paralela a Y.
+    pub fn orbit(&mut self, yaw: f32, pitch: f32) {
+        let (un, _, _) = self.basis();
+        let offset: Vec3 = self.vrp - self.p;
+
+        let yaw_rotation = Rotation3::from_axis_angle(&Unit::new_normalize(self.y), yaw);
+        let pitch_rotation = Rotation3::from_axis_angle(&Unit::new_normalize(un), pitch);
+
+        let rotated: Vec3 = yaw_rotation * (pitch_rotation * offset);
+        let sin = rotated.normalize().cross(&self.y.normalize()).norm();
+
+        self.vrp = if sin > MIN_ORBIT_SIN {
+            self.p + rotated
+        } else {
+            self.p + yaw_rotation * offset
+        };
+    }
+
+    /// Translada VRP e P ao longo dos eixos u e v do SRC.
+    pub fn pan(&mut self, du: f32, dv: f32) {
+        let (un, vn, _) = self.basis();
+        let delta: Vec3 = un * du + vn * dv;
+        self.vrp += delta;
+        self.p += delta;
+    }
+
+    /// Multiplica a distância entre o VRP e P pelo fator, mantendo P entre os planos próximo e
+    /// distante.
+    pub fn dolly(&mut self, factor: f32) {
+        let offset: Vec3 = (self.vrp - self.p) * factor;
+        if offset.norm() > self.near && offset.norm() < self.far {
+            self.vrp = self.p + offset;
+        }
+    }
+}
@@ -0,0 +1,113 @@
+use std::f32::consts::FRAC_1_SQRT_2;
+
+use crate::object::Object;
+use crate::types::{Mat4x1, Vec3};
+use crate::utils::weighted_point;
+
+/// Ordem das curvas cônicas racionais (quadráticas).
+const CONIC_ORDER: usize = 3;
+
+/// Círculo unitário completo como B-spline racional quadrática: 9 pontos nos vértices e nos
+/// pontos médios das arestas do quadrado circunscrito, com peso `1/√2` nos cantos.
+const CIRCLE: [(f32, f32, f32); 9] = [
+    (1.0, 0.0, 1.0),
+    (1.0, 1.0, FRAC_1_SQRT_2),
+    (0.0, 1.0, 1.0),
+    (-1.0, 1.0, FRAC_1_SQRT_2),
+    (-1.0, 0.0, 1.0),
+    (-1.0, -1.0, FRAC_1_SQRT_2),
+    (0.0, -1.0, 1.0),
+    (1.0, -1.0, FRAC_1_SQRT_2),
+    (1.0, 0.0, 1.0),
+];
+const CIRCLE_KNOTS: [f32; 12] = [0.0, 0.0, 0.0, 1.0, 1.0, 2.0, 2.0, 3.0, 3.0, 4.0, 4.0, 4.0];
+
+/// Semicírculo unitário de -90° a 90°, com os mesmos pesos do círculo.
+const SEMICIRCLE: [(f32, f32, f32); 5] = [
+    (0.0, -1.0, 1.0),
+    (1.0, -1.0, FRAC_1_SQRT_2),
+    (1.0, 0.0, 1.0),
+    (1.0, 1.0, FRAC_1_SQRT_2),
+    (0.0, 1.0, 1.0),
+];
+const SEMICIRCLE_KNOTS: [f32; 8] = [0.0, 0.0, 0.0, 1.0, 1.0, 2.0, 2.0, 2.0];
+
+/// Superfícies quádricas representadas exatamente por malhas de controle com pesos.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Conic {
+    /// Cilindro em torno do eixo z, de z = 0 até `height`.
+    Cylinder { radius: f32, height: f32 },
+    /// Esfera centrada na origem.
+    Sphere { radius: f32 },
+    /// Toro em torno do eixo z, centrado na origem.
+    Torus { major_radius: f32, minor_radius: f32 },
+}
+
+impl Conic {
+    /// Nome da superfície exibido na interface.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Conic::Cylinder { .. } => "Cilindro",
+            Conic::Sphere { .. } => "Esfera",
+            Conic::Torus { .. } => "Toro",
+        }
+    }
+
+    /// Cria a superfície de revolução em torno do eixo z, com a resolução informada na
+    /// direção angular (i) e ao longo do perfil (j).
+    pub fn to_object(&self, resi: usize, resj: usize) -> Result<Object, String> {
+        match *self {
+            Conic::Cylinder { radius, height } => {
+                if radius <= 0.0 || height <= 0.0 {
+                    return Err("O raio e a altura do cilindro devem ser positivos.".to_string());
+                }
+                let profile = [(radius, 0.0, 1.0), (radius, height, 1.0)];
+                revolve(&profile, 2, vec![0.0, 0.0, 1.0, 1.0], resi, resj)
+            }
+            Conic::Sphere { radius } => {
+                if radius <= 0.0 {
+                    return Err("O raio da esfera deve ser positivo.".to_string());
+                }
+                let profile = SEMICIRCLE.map(|(r, z, w)| (r * radius, z * radius, w));
+                revolve(&profile, CONIC_ORDER, SEMICIRCLE_KNOTS.to_vec(), resi, resj)
+            }
+            Conic::Torus { major_radius, minor_radius } => {
+                if minor_radius <= 0.0 || major_radius <= minor_radius {
+                    return Err("O toro deve ter 0 < raio menor < raio maior.".to_string());
+                }
+                let profile = CIRCLE.map(|(r, z, w)| (major_radius + r * minor_radius, z * minor_radius, w));
+                revolve(&profile, CONIC_ORDER, CIRCLE_KNOTS.to_vec(), resi, resj)
+            }
+        }
+    }
+}
+
+/// Gira o perfil `(r, z, peso)` do plano xz em torno do eixo z. O ponto de controle (i, j) é
+/// o ponto j do perfil girado até o ponto i do círculo, com o produto dos pesos.
+fn revolve(
+    profile: &[(f32, f32, f32)],
+    profile_order: usize,
+    profile_knots: Vec<f32>,
+    resi: usize,
+    resj: usize,
+) -> Result<Object, String> {
+    let mut control_points: Vec<Mat4x1> = Vec::with_capacity(CIRCLE.len() * profile.len());
+    for &(cx, cy, wi) in &CIRCLE {
+        for &(r, z, wj) in profile {
+            control_points.push(weighted_point(&Vec3::new(r * cx, r * cy, z), wi * wj));
+        }
+    }
+
+    let mut object = Object::from_control_points(
+        CIRCLE.len() - 1,
+        profile.len() - 1,
+        CONIC_ORDER,
+        profile_order,
+        resi,
+        resj,
+        control_points,
+    )?;
+    object.set_knots(CIRCLE_KNOTS.to_vec(), profile_knots)?;
+
+    Ok(object)
+}
@@ -1,4 +1,6 @@
 pub const GUI_WIDTH: f32 = 1000.0;
 pub const GUI_HEIGHT: f32 = 600.0;
 pub const GUI_SIDEBAR_WIDTH: f32 = 300.0;
-pub const GUI_VECTOR_INPUT_WIDTH: f32 = 50.0;
\ No newline at end of file
+pub const GUI_VECTOR_INPUT_WIDTH: f32 = 50.0;
+pub const GUI_ORBIT_SPEED: f32 = 0.01;
+pub const GUI_ZOOM_SPEED: f32 = 0.002;
\ No newline at end of file
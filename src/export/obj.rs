@@ -0,0 +1,151 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use crate::object::Object;
+use crate::types::Vec3;
+use crate::utils::mat4x1_to_vec3;
+
+/// Escreve as malhas dos objetos no formato Wavefront OBJ, com um grupo por objeto.
+///
+/// Cada vértice recebe o seu vetor normal (`vn`) e as suas coordenadas paramétricas (u, v)
+/// como coordenadas de textura (`vt`). Se a triangulação estiver habilitada, são escritos os
+/// triângulos; caso contrário, as faces quadriláteras.
+pub fn write_obj<W: Write>(mut writer: W, objects: &[Object]) -> io::Result<()> {
+    writeln!(writer, "# Aleluia")?;
+
+    // Os índices do OBJ são globais ao arquivo e começam em 1
+    let mut offset = 1;
+
+    for (k, object) in objects.iter().enumerate() {
+        let vertices = object.get_vertices();
+        let normals = object.get_normals();
+
+        writeln!(writer, "o objeto_{}", k + 1)?;
+        writeln!(writer, "g objeto_{}", k + 1)?;
+
+        for vertex in vertices {
+            let position: Vec3 = mat4x1_to_vec3(vertex);
+            writeln!(writer, "v {} {} {}", position.x, position.y, position.z)?;
+        }
+        for [u, v] in object.get_uvs() {
+            writeln!(writer, "vt {u} {v}")?;
+        }
+        for normal in normals {
+            writeln!(writer, "vn {} {} {}", normal.x, normal.y, normal.z)?;
+        }
+
+        match object.get_triangles() {
+            Some(triangles) => {
+                for triangle in triangles {
+                    write_face(&mut writer, triangle, offset)?;
+                }
+            }
+            None => {
+                for face in object.get_faces() {
+                    write_face(&mut writer, face, offset)?;
+                }
+            }
+        }
+
+        offset += vertices.len();
+    }
+
+    writer.flush()
+}
+
+/// Escreve uma face, com os mesmos índices para posição, textura e normal.
+fn write_face<W: Write>(writer: &mut W, face: &[usize], offset: usize) -> io::Result<()> {
+    write!(writer, "f")?;
+    for &i in face {
+        let index = i + offset;
+        write!(writer, " {index}/{index}/{index}")?;
+    }
+    writeln!(writer)
+}
+
+/// Salva as malhas dos objetos em um arquivo OBJ.
+pub fn save_obj(path: &Path, objects: &[Object]) -> io::Result<()> {
+    write_obj(BufWriter::new(File::create(path)?), objects)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::Generator;
+    use crate::object::DiagonalSplit;
+
+    fn lines<'a>(obj: &'a str, prefix: &str) -> Vec<Vec<&'a str>> {
+        obj.lines()
+            .filter_map(|line| line.strip_prefix(prefix))
+            .map(|line| line.split_whitespace().collect())
+            .collect()
+    }
+
+    #[test]
+    fn writes_each_object_with_global_indices() {
+        let generator = Generator::Random { seed: 5, amplitude: 3.0 };
+        let quads = Object::from_generator(&generator, 4, 3, 3, 3, 6, 5).unwrap();
+        let mut triangles = Object::from_generator(&generator, 3, 3, 2, 2, 4, 4).unwrap();
+        triangles.set_triangulation(Some(DiagonalSplit::Alternating));
+
+        let mut buffer = Vec::new();
+        write_obj(&mut buffer, &[quads, triangles]).unwrap();
+        let obj = String::from_utf8(buffer).unwrap();
+
+        assert_eq!(lines(&obj, "o ").len(), 2);
+        assert_eq!(lines(&obj, "v ").len(), 30 + 16);
+        assert_eq!(lines(&obj, "vt ").len(), 30 + 16);
+        assert_eq!(lines(&obj, "vn ").len(), 30 + 16);
+
+        let faces = lines(&obj, "f ");
+        assert_eq!(faces.len(), 5 * 4 + 2 * 3 * 3);
+
+        // Quadriláteros do primeiro objeto, triângulos do segundo, com índices a partir de 31
+        let (first, second) = faces.split_at(20);
+        assert!(first.iter().all(|face| face.len() == 4));
+        assert!(second.iter().all(|face| face.len() == 3));
+        assert_eq!(first[0], ["1/1/1", "6/6/6", "7/7/7", "2/2/2"]);
+
+        let indices = |faces: &[Vec<&str>]| -> Vec<usize> {
+            faces
+                .iter()
+                .flatten()
+                .map(|vertex| {
+                    let [v, vt, vn]: [usize; 3] = vertex
+                        .split('/')
+                        .map(|index| index.parse().unwrap())
+                        .collect::<Vec<_>>()
+                        .try_into()
+                        .unwrap();
+                    assert!(v == vt && v == vn, "{vertex}");
+                    v
+                })
+                .collect()
+        };
+        assert!(indices(first).iter().all(|&index| (1..=30).contains(&index)));
+        assert!(indices(second).iter().all(|&index| (31..=46).contains(&index)));
+    }
+
+    #[test]
+    fn positions_and_normals_match_the_mesh() {
+        let generator = Generator::Sine { amplitude: 2.0, waves_i: 1.0, waves_j: 2.0 };
+        let object = Object::from_generator(&generator, 5, 5, 3, 3, 8, 8).unwrap();
+
+        let mut buffer = Vec::new();
+        write_obj(&mut buffer, std::slice::from_ref(&object)).unwrap();
+        let obj = String::from_utf8(buffer).unwrap();
+
+        // O Display de f32 é exato na leitura de volta
+        let parse = |values: &[&str]| -> Vec3 {
+            let [x, y, z] = [0, 1, 2].map(|k| values[k].parse().unwrap());
+            Vec3::new(x, y, z)
+        };
+        for (values, vertex) in lines(&obj, "v ").iter().zip(object.get_vertices()) {
+            assert_eq!(parse(values), mat4x1_to_vec3(vertex));
+        }
+        for (values, normal) in lines(&obj, "vn ").iter().zip(object.get_normals()) {
+            assert_eq!(parse(values), *normal);
+        }
+    }
+}
@@ -0,0 +1,296 @@
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use crate::object::Object;
+use crate::render::Render;
+use crate::types::{Mat4x1, Vec3};
+use crate::utils::{mat4x1_to_vec3, vec3_to_mat4x1, weld_tolerance};
+
+/// Codificação do arquivo STL.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum StlFormat {
+    Ascii,
+    Binary,
+}
+
+/// Triangula a malha do objeto. Usa os triângulos do objeto, se a triangulação estiver
+/// habilitada; caso contrário, divide cada face pela diagonal do primeiro ao terceiro vértice.
+pub fn triangulate(object: &Object) -> (Vec<Vec3>, Vec<[usize; 3]>) {
+    let positions: Vec<Vec3> = object.get_vertices().iter().map(mat4x1_to_vec3).collect();
+
+    let triangles: Vec<[usize; 3]> = match object.get_triangles() {
+        Some(triangles) => triangles.to_vec(),
+        None => object
+            .get_faces()
+            .iter()
+            .flat_map(|&[a, b, c, d]| [[a, b, c], [a, c, d]])
+            .collect(),
+    };
+
+    (positions, triangles)
+}
+
+/// Solda os vértices que ocupam a mesma posição (a menos de `utils::weld_tolerance`) e
+/// descarta os triângulos degenerados.
+///
+/// As superfícies fechadas, como as cônicas, repetem os vértices da costura e dos polos; sem a
+/// solda, essas arestas parecem bordas e os triângulos dos polos têm área nula.
+pub fn weld(positions: &[Vec3], triangles: &[[usize; 3]]) -> (Vec<Vec3>, Vec<[usize; 3]>) {
+    let tolerance = weld_tolerance(positions);
+
+    // Grade de células do tamanho da tolerância: vértices próximos estão em células vizinhas
+    let cell = |p: &Vec3| (p / tolerance).map(|c| c.floor() as i64);
+    let mut grid: HashMap<(i64, i64, i64), Vec<usize>> = HashMap::new();
+    let mut welded_positions: Vec<Vec3> = Vec::with_capacity(positions.len());
+
+    let remap: Vec<usize> = positions
+        .iter()
+        .map(|p| {
+            let c = cell(p);
+            let neighbours = (-1..=1).flat_map(|dx| {
+                (-1..=1).flat_map(move |dy| (-1..=1).map(move |dz| (c.x + dx, c.y + dy, c.z + dz)))
+            });
+
+            let existing = neighbours
+                .filter_map(|key| grid.get(&key))
+                .flatten()
+                .copied()
+                .find(|&k| (welded_positions[k] - p).norm() <= tolerance);
+
+            existing.unwrap_or_else(|| {
+                let k = welded_positions.len();
+                welded_positions.push(*p);
+                grid.entry((c.x, c.y, c.z)).or_default().push(k);
+                k
+            })
+        })
+        .collect();
+
+    let welded_triangles: Vec<[usize; 3]> = triangles
+        .iter()
+        .map(|triangle| triangle.map(|k| remap[k]))
+        .filter(|&[a, b, c]| {
+            let [pa, pb, pc] = [a, b, c].map(|k| welded_positions[k]);
+            a != b && b != c && c != a && (pb - pa).cross(&(pc - pa)).norm() > tolerance * tolerance
+        })
+        .collect();
+
+    (welded_positions, welded_triangles)
+}
+
+/// Engrossa a superfície em um sólido fechado de espessura `thickness`.
+///
+/// A malha é antes soldada (ver `weld`). Os vértices são deslocados no sentido oposto às suas
+/// normais, formando a face interna com a orientação invertida, e as arestas da borda (usadas
+/// por um único triângulo) são ligadas à face interna por quadriláteros. Com espessura
+/// negativa, o deslocamento é no sentido das normais e a orientação de todo o sólido é
+/// invertida, para que as normais apontem para fora.
+pub fn thicken(
+    positions: &[Vec3],
+    triangles: &[[usize; 3]],
+    thickness: f32,
+) -> (Vec<Vec3>, Vec<[usize; 3]>) {
+    let (positions, triangles) = weld(positions, triangles);
+    let (positions, triangles) = (positions.as_slice(), triangles.as_slice());
+
+    let n = positions.len();
+    let vertices: Vec<Mat4x1> = positions.iter().map(vec3_to_mat4x1).collect();
+    let normals: Vec<Vec3> = Render::calc_vertex_normals(&vertices, triangles);
+
+    let mut solid_positions: Vec<Vec3> = Vec::with_capacity(2 * n);
+    solid_positions.extend_from_slice(positions);
+    solid_positions.extend(positions.iter().zip(&normals).map(|(p, normal)| p - normal * thickness));
+
+    let mut solid_triangles: Vec<[usize; 3]> = Vec::with_capacity(2 * triangles.len());
+    solid_triangles.extend_from_slice(triangles);
+    solid_triangles.extend(triangles.iter().map(|&[a, b, c]| [a + n, c + n, b + n]));
+
+    // Arestas orientadas; uma aresta da borda não tem a sua oposta em outro triângulo
+    let edges: HashSet<(usize, usize)> = triangles
+        .iter()
+        .flat_map(|&[a, b, c]| [(a, b), (b, c), (c, a)])
+        .collect();
+
+    for (a, b) in triangles.iter().flat_map(|&[a, b, c]| [(a, b), (b, c), (c, a)]) {
+        if !edges.contains(&(b, a)) {
+            solid_triangles.push([b, a, a + n]);
+            solid_triangles.push([b, a + n, b + n]);
+        }
+    }
+
+    if thickness < 0.0 {
+        for triangle in &mut solid_triangles {
+            triangle.swap(1, 2);
+        }
+    }
+
+    (solid_positions, solid_triangles)
+}
+
+/// Escreve as malhas dos objetos no formato STL. Se `thickness` for informada, cada
+/// superfície é engrossada em um sólido fechado (ver `thicken`); a espessura deve ser
+/// positiva, pois com espessura nula as duas faces do sólido se sobrepõem.
+pub fn write_stl<W: Write>(
+    mut writer: W,
+    objects: &[Object],
+    format: StlFormat,
+    thickness: Option<f32>,
+) -> io::Result<()> {
+    if let Some(thickness) = thickness.filter(|thickness| *thickness <= 0.0 || !thickness.is_finite()) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("Espessura inválida: {thickness}. Deve ser positiva."),
+        ));
+    }
+
+    let mut facets: Vec<[Vec3; 3]> = Vec::new();
+
+    for object in objects {
+        let (mut positions, mut triangles) = triangulate(object);
+        if let Some(thickness) = thickness {
+            (positions, triangles) = thicken(&positions, &triangles, thickness);
+        }
+
+        facets.extend(
+            triangles
+                .iter()
+                .map(|&[a, b, c]| [positions[a], positions[b], positions[c]]),
+        );
+    }
+
+    match format {
+        StlFormat::Ascii => write_ascii(&mut writer, &facets)?,
+        StlFormat::Binary => write_binary(&mut writer, &facets)?,
+    }
+
+    writer.flush()
+}
+
+/// Salva as malhas dos objetos em um arquivo STL.
+pub fn save_stl(
+    path: &Path,
+    objects: &[Object],
+    format: StlFormat,
+    thickness: Option<f32>,
+) -> io::Result<()> {
+    write_stl(BufWriter::new(File::create(path)?), objects, format, thickness)
+}
+
+fn write_ascii<W: Write>(writer: &mut W, facets: &[[Vec3; 3]]) -> io::Result<()> {
+    writeln!(writer, "solid aleluia")?;
+    for facet in facets {
+        let normal = calc_facet_normal(facet);
+        writeln!(writer, "  facet normal {} {} {}", normal.x, normal.y, normal.z)?;
+        writeln!(writer, "    outer loop")?;
+        for vertex in facet {
+            writeln!(writer, "      vertex {} {} {}", vertex.x, vertex.y, vertex.z)?;
+        }
+        writeln!(writer, "    endloop")?;
+        writeln!(writer, "  endfacet")?;
+    }
+    writeln!(writer, "endsolid aleluia")
+}
+
+fn write_binary<W: Write>(writer: &mut W, facets: &[[Vec3; 3]]) -> io::Result<()> {
+    let count = u32::try_from(facets.len())
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "Triângulos demais para o STL"))?;
+
+    let mut header = [0u8; 80];
+    let title = b"aleluia";
+    header[..title.len()].copy_from_slice(title);
+    writer.write_all(&header)?;
+    writer.write_all(&count.to_le_bytes())?;
+
+    for facet in facets {
+        let normal = calc_facet_normal(facet);
+        for vector in [&normal, &facet[0], &facet[1], &facet[2]] {
+            for component in vector.iter() {
+                writer.write_all(&component.to_le_bytes())?;
+            }
+        }
+        // Contagem de bytes de atributos, sem uso
+        writer.write_all(&0u16.to_le_bytes())?;
+    }
+
+    Ok(())
+}
+
+/// Calcula a normal unitária do triângulo pela regra da mão direita, ou zero se degenerado.
+fn calc_facet_normal([a, b, c]: &[Vec3; 3]) -> Vec3 {
+    (b - a)
+        .cross(&(c - a))
+        .try_normalize(f32::EPSILON)
+        .unwrap_or_else(Vec3::zeros)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::conic::Conic;
+    use crate::generator::Generator;
+
+    /// Verifica se cada aresta orientada aparece uma única vez e tem a sua oposta, ou seja, se a
+    /// malha é fechada e orientada de forma consistente, sem triângulos degenerados.
+    fn assert_closed((positions, triangles): (Vec<Vec3>, Vec<[usize; 3]>)) {
+        let mut edges: HashMap<(usize, usize), usize> = HashMap::new();
+        for &[a, b, c] in &triangles {
+            let [pa, pb, pc] = [a, b, c].map(|k| positions[k]);
+            assert!((pb - pa).cross(&(pc - pa)).norm() > 0.0, "triângulo degenerado");
+            for edge in [(a, b), (b, c), (c, a)] {
+                *edges.entry(edge).or_default() += 1;
+            }
+        }
+
+        for (&(a, b), &count) in &edges {
+            assert_eq!(count, 1, "aresta ({a}, {b}) repetida");
+            assert_eq!(edges.get(&(b, a)), Some(&1), "aresta ({a}, {b}) sem a oposta");
+        }
+    }
+
+    #[test]
+    fn thickened_surfaces_are_closed() {
+        let objects = [
+            Object::from_generator(&Generator::Random { seed: 1, amplitude: 10.0 }, 10, 10, 3, 3, 20, 20)
+                .unwrap(),
+            Conic::Cylinder { radius: 3.0, height: 6.0 }.to_object(20, 20).unwrap(),
+            Conic::Sphere { radius: 4.0 }.to_object(16, 8).unwrap(),
+            Conic::Torus { major_radius: 5.0, minor_radius: 1.5 }.to_object(20, 20).unwrap(),
+        ];
+
+        for object in &objects {
+            let (positions, triangles) = triangulate(object);
+            assert_closed(thicken(&positions, &triangles, 0.3));
+            assert_closed(thicken(&positions, &triangles, -0.3));
+        }
+    }
+
+    #[test]
+    fn rejects_non_positive_thickness() {
+        let sphere = Conic::Sphere { radius: 4.0 }.to_object(16, 8).unwrap();
+
+        for thickness in [0.0, -0.3, f32::NAN] {
+            let mut buffer = Vec::new();
+            let error = write_stl(&mut buffer, std::slice::from_ref(&sphere), StlFormat::Binary, Some(thickness))
+                .unwrap_err();
+            assert_eq!(error.kind(), io::ErrorKind::InvalidInput, "{thickness}");
+            assert!(buffer.is_empty());
+        }
+
+        let mut buffer = Vec::new();
+        assert!(write_stl(&mut buffer, &[sphere], StlFormat::Binary, Some(0.3)).is_ok());
+    }
+
+    #[test]
+    fn weld_merges_seams_and_drops_degenerate_triangles() {
+        let sphere = Conic::Sphere { radius: 4.0 }.to_object(16, 8).unwrap();
+        let (positions, triangles) = triangulate(&sphere);
+        let (welded_positions, welded_triangles) = weld(&positions, &triangles);
+
+        // Costura (16 x 8 → 15 x 8) e polos (15 vértices cada → 1)
+        assert_eq!(welded_positions.len(), 15 * 6 + 2);
+        // Um triângulo de cada quadrilátero dos polos tem área nula
+        assert_eq!(welded_triangles.len(), triangles.len() - 2 * 15);
+    }
+}
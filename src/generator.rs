@@ -0,0 +1,219 @@
+use std::f32::consts::TAU;
+
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use crate::types::Mat4x1;
+
+/// Gerador das alturas (z) da grade de pontos de controle. Os pontos ficam nas posições
+/// inteiras `(i, j)` do plano xy.
+///
+/// Os geradores com semente usam o ChaCha8, cujo resultado é fixo entre plataformas e versões
+/// das bibliotecas, para que uma semente sempre reproduza a mesma superfície.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Generator {
+    /// Alturas aleatórias uniformes em `[0, amplitude)`, reprodutíveis pela semente.
+    Random { seed: u64, amplitude: f32 },
+    /// Plano horizontal na altura informada.
+    Plane { height: f32 },
+    /// Terreno de ruído de Perlin fractal, com alturas em `[0, amplitude]`.
+    Noise { seed: u64, amplitude: f32, frequency: f32, octaves: usize },
+    /// Ondas senoidais, com o número de ondas informado em cada direção da grade.
+    Sine { amplitude: f32, waves_i: f32, waves_j: f32 },
+    /// Paraboloide centrado na grade, com altura `amplitude` nos cantos.
+    Paraboloid { amplitude: f32 },
+}
+
+impl Generator {
+    /// Nome do gerador exibido na interface.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Generator::Random { .. } => "Aleatório",
+            Generator::Plane { .. } => "Plano",
+            Generator::Noise { .. } => "Ruído de Perlin",
+            Generator::Sine { .. } => "Senoide",
+            Generator::Paraboloid { .. } => "Paraboloide",
+        }
+    }
+
+    /// Gera a grade de `(ni + 1) x (nj + 1)` pontos de controle, linha a linha.
+    pub fn control_points(&self, ni: usize, nj: usize) -> Vec<Mat4x1> {
+        let heights: Vec<f32> = match *self {
+            Generator::Random { seed, amplitude } => {
+                let mut rng = ChaCha8Rng::seed_from_u64(seed);
+                (0..(ni + 1) * (nj + 1))
+                    .map(|_| if amplitude > 0.0 { rng.gen_range(0.0..amplitude) } else { 0.0 })
+                    .collect()
+            }
+            Generator::Plane { height } => vec![height; (ni + 1) * (nj + 1)],
+            Generator::Noise { seed, amplitude, frequency, octaves } => {
+                let perlin = Perlin::new(seed);
+                Self::grid(ni, nj, |x, y| {
+                    amplitude * (perlin.fractal(x * frequency, y * frequency, octaves) + 1.0) / 2.0
+                })
+            }
+            Generator::Sine { amplitude, waves_i, waves_j } => {
+                let (ni_f, nj_f) = (ni.max(1) as f32, nj.max(1) as f32);
+                Self::grid(ni, nj, |x, y| {
+                    amplitude * ((TAU * waves_i * x / ni_f).sin() + (TAU * waves_j * y / nj_f).sin()) / 2.0
+                })
+            }
+            Generator::Paraboloid { amplitude } => {
+                let (ci, cj) = (ni as f32 / 2.0, nj as f32 / 2.0);
+                let corner = (ci * ci + cj * cj).max(f32::EPSILON);
+                Self::grid(ni, nj, |x, y| {
+                    amplitude * ((x - ci).powi(2) + (y - cj).powi(2)) / corner
+                })
+            }
+        };
+
+        let mut control_points: Vec<Mat4x1> = Vec::with_capacity((ni + 1) * (nj + 1));
+        for i in 0..=ni {
+            for j in 0..=nj {
+                control_points.push(Mat4x1::new(i as f32, j as f32, heights[i * (nj + 1) + j], 1.0));
+            }
+        }
+        control_points
+    }
+
+    /// Avalia a altura em cada posição `(i, j)` da grade, linha a linha.
+    fn grid(ni: usize, nj: usize, height: impl Fn(f32, f32) -> f32) -> Vec<f32> {
+        (0..=ni)
+            .flat_map(|i| (0..=nj).map(move |j| (i as f32, j as f32)))
+            .map(|(x, y)| height(x, y))
+            .collect()
+    }
+}
+
+/// Ruído de Perlin bidimensional, com a tabela de permutação embaralhada pela semente.
+struct Perlin {
+    permutation: [u8; 512],
+    /// Deslocamento fracionário das amostras, sorteado pela semente. O ruído de Perlin é nulo
+    /// nos pontos inteiros do reticulado, onde ficam os pontos de controle com frequências
+    /// inteiras; o deslocamento tira as amostras de lá.
+    offset: (f32, f32),
+}
+
+impl Perlin {
+    fn new(seed: u64) -> Self {
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+
+        let mut values: Vec<u8> = (0..=255).collect();
+        values.shuffle(&mut rng);
+
+        let mut permutation = [0u8; 512];
+        for (k, value) in permutation.iter_mut().enumerate() {
+            *value = values[k % 256];
+        }
+
+        let offset = (rng.gen_range(0.25..0.75), rng.gen_range(0.25..0.75));
+
+        Self { permutation, offset }
+    }
+
+    /// Ruído no ponto (x, y), aproximadamente em `[-1, 1]`.
+    fn noise(&self, x: f32, y: f32) -> f32 {
+        let (x, y) = (x + self.offset.0, y + self.offset.1);
+        let (x0, y0) = (x.floor(), y.floor());
+        let (xf, yf) = (x - x0, y - y0);
+        let xi = (x0 as i64 & 255) as usize;
+        let yi = (y0 as i64 & 255) as usize;
+
+        let p = &self.permutation;
+        let hash = |i: usize, j: usize| p[p[xi + i] as usize + yi + j];
+
+        let u = Self::fade(xf);
+        let v = Self::fade(yf);
+
+        let n00 = Self::gradient(hash(0, 0), xf, yf);
+        let n10 = Self::gradient(hash(1, 0), xf - 1.0, yf);
+        let n01 = Self::gradient(hash(0, 1), xf, yf - 1.0);
+        let n11 = Self::gradient(hash(1, 1), xf - 1.0, yf - 1.0);
+
+        let nx0 = n00 + u * (n10 - n00);
+        let nx1 = n01 + u * (n11 - n01);
+        nx0 + v * (nx1 - nx0)
+    }
+
+    /// Soma de oitavas do ruído, cada uma com o dobro da frequência e metade da amplitude,
+    /// normalizada para `[-1, 1]`.
+    fn fractal(&self, x: f32, y: f32, octaves: usize) -> f32 {
+        let mut sum = 0.0;
+        let mut total = 0.0;
+        let mut amplitude = 1.0;
+        let mut frequency = 1.0;
+
+        for _ in 0..octaves.max(1) {
+            sum += amplitude * self.noise(x * frequency, y * frequency);
+            total += amplitude;
+            amplitude *= 0.5;
+            frequency *= 2.0;
+        }
+
+        (sum / total).clamp(-1.0, 1.0)
+    }
+
+    #[inline(always)]
+    fn fade(t: f32) -> f32 {
+        t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+    }
+
+    #[inline(always)]
+    fn gradient(hash: u8, x: f32, y: f32) -> f32 {
+        match hash & 7 {
+            0 => x + y,
+            1 => -x + y,
+            2 => x - y,
+            3 => -x - y,
+            4 => x,
+            5 => -x,
+            6 => y,
+            _ => -y,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn heights(generator: &Generator, ni: usize, nj: usize) -> Vec<f32> {
+        generator.control_points(ni, nj).iter().map(|point| point.z).collect()
+    }
+
+    #[test]
+    fn noise_with_integer_frequency_is_not_flat() {
+        for frequency in [1.0, 2.0, 3.0] {
+            let generator = Generator::Noise { seed: 1, amplitude: 10.0, frequency, octaves: 1 };
+            let heights = heights(&generator, 5, 5);
+
+            let (min, max) = heights
+                .iter()
+                .fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), &z| (min.min(z), max.max(z)));
+            assert!(max - min > 1.0, "frequência {frequency}: alturas entre {min} e {max}");
+        }
+    }
+
+    #[test]
+    fn noise_rows_off_the_lattice_are_not_pinned() {
+        // Com frequência 0.2, as linhas múltiplas de 5 caem nos pontos inteiros do reticulado
+        let generator = Generator::Noise { seed: 1, amplitude: 10.0, frequency: 0.2, octaves: 1 };
+        let heights = heights(&generator, 10, 10);
+
+        let row: Vec<f32> = heights[5 * 11..6 * 11].to_vec();
+        assert!(row.iter().any(|&z| (z - 5.0).abs() > 0.01), "{row:?}");
+    }
+
+    #[test]
+    fn seeded_generators_are_reproducible() {
+        let random = Generator::Random { seed: 42, amplitude: 10.0 };
+        assert_eq!(heights(&random, 4, 4), heights(&random, 4, 4));
+
+        // Valores fixos do ChaCha8: mudam apenas se o algoritmo mudar
+        let expected = [2.2408068, 6.818961, 1.4638615, 9.502753];
+        let actual = heights(&random, 1, 1);
+        for (a, e) in actual.iter().zip(expected) {
+            assert!((a - e).abs() < 1e-5, "{actual:?}");
+        }
+    }
+}
@@ -0,0 +1,149 @@
+use std::path::Path;
+
+use crate::generator::Generator;
+use crate::object::{DiagonalSplit, Object};
+use crate::render::{ProjectionType, Render, ShaderType, Viewport};
+use crate::render::frame_buffer::FrameBuffer;
+use crate::types::Vec3;
+use crate::utils::mat4x1_to_vec3;
+
+/// Instruções de uso do subcomando `render`.
+pub const USAGE: &str = "Uso: aleluia render <saida.png|saida.ppm> [--width N] [--height N] \
+[--projection orthographic|perspective] [--shader wireframe|constant|gouraud|phong] [--hidden-lines] \
+[--triangulate fixed|shortest|alternating] [--seed N]";
+
+/// Rasteriza os objetos em um buffer de quadro fora da tela, com as dimensões informadas.
+///
+/// A window de uma cópia do render é expandida em torno do centro até a proporção da imagem,
+/// para que a cena não seja esticada quando a imagem não tiver a proporção da window. O render
+/// informado não é alterado, então renderizações repetidas produzem a mesma imagem.
+pub fn render_offscreen(objects: &[Object], render: &Render, width: usize, height: usize) -> FrameBuffer {
+    let mut render = render.clone();
+
+    let viewport = Viewport {
+        umin: 0.0,
+        umax: width.max(1) as f32 - 1.0,
+        vmin: 0.0,
+        vmax: height.max(1) as f32 - 1.0,
+    };
+
+    let mut window = render.get_window().clone();
+    // A transformação da window para a viewport leva a window em (largura - 1) x (altura - 1)
+    let aspect = (width.max(2) - 1) as f32 / (height.max(2) - 1) as f32;
+    window.fit_aspect(aspect);
+    render.set_window(window);
+    render.set_viewport(viewport);
+
+    let mut frame_buffer = FrameBuffer::from_viewport(render.get_viewport());
+    render.render_objects(&mut frame_buffer, objects);
+    frame_buffer
+}
+
+/// Rasteriza os objetos e salva a imagem em um arquivo PNG ou PPM, conforme a extensão.
+pub fn render_to_file(
+    objects: &[Object],
+    render: &Render,
+    width: usize,
+    height: usize,
+    path: &Path,
+) -> std::io::Result<()> {
+    render_offscreen(objects, render, width, height).save(path)
+}
+
+/// Executa o subcomando `render`, que gera uma imagem da cena sem abrir uma janela.
+pub fn run(args: &[String]) -> Result<(), String> {
+    let mut output: Option<&str> = None;
+    let mut width: usize = 640;
+    let mut height: usize = 480;
+    let mut projection_type = ProjectionType::Perspective;
+    let mut shader_type = ShaderType::Phong;
+    let mut hidden_lines = false;
+    let mut triangulation: Option<DiagonalSplit> = None;
+    let mut seed: Option<u64> = None;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("Faltou o valor de {arg}.\n{USAGE}"));
+
+        match arg.as_str() {
+            "--width" => width = parse_size(value()?)?,
+            "--height" => height = parse_size(value()?)?,
+            "--projection" => {
+                projection_type = match value()?.as_str() {
+                    "orthographic" => ProjectionType::Orthographic,
+                    "perspective" => ProjectionType::Perspective,
+                    other => return Err(format!("Projeção inválida: {other}.\n{USAGE}")),
+                }
+            }
+            "--shader" => {
+                shader_type = match value()?.as_str() {
+                    "wireframe" => ShaderType::Wireframe,
+                    "constant" => ShaderType::Constant,
+                    "gouraud" => ShaderType::Gouraud,
+                    "phong" => ShaderType::Phong,
+                    other => return Err(format!("Sombreamento inválido: {other}.\n{USAGE}")),
+                }
+            }
+            "--hidden-lines" => hidden_lines = true,
+            "--triangulate" => {
+                triangulation = match value()?.as_str() {
+                    "fixed" => Some(DiagonalSplit::Fixed),
+                    "shortest" => Some(DiagonalSplit::Shortest),
+                    "alternating" => Some(DiagonalSplit::Alternating),
+                    other => return Err(format!("Triangulação inválida: {other}.\n{USAGE}")),
+                }
+            }
+            "--seed" => {
+                let value = value()?;
+                seed = Some(value.parse().map_err(|_| format!("Semente inválida: {value}.\n{USAGE}"))?);
+            }
+            _ if output.is_none() && !arg.starts_with("--") => output = Some(arg),
+            _ => return Err(format!("Argumento inválido: {arg}.\n{USAGE}")),
+        }
+    }
+
+    let output = output.ok_or(USAGE.to_string())?;
+
+    let seed = seed.unwrap_or_else(|| {
+        let seed = rand::random();
+        eprintln!("Semente: {seed} (use --seed {seed} para reproduzir a superfície)");
+        seed
+    });
+    let generator = Generator::Random { seed, amplitude: 10.0 };
+    let mut object = Object::from_generator(&generator, 10, 10, 3, 3, 20, 20)?;
+    object.set_triangulation(triangulation);
+    let objects = vec![object];
+
+    let mut render = Render::default();
+    let mut camera = render.get_camera().clone();
+    camera.p = calc_center(&objects);
+    render.set_camera(camera);
+    render.set_projection(projection_type);
+    render.set_shader(shader_type);
+    render.set_hidden_lines(hidden_lines);
+
+    render_to_file(&objects, &render, width, height, Path::new(output))
+        .map_err(|error| format!("Não foi possível salvar {output}: {error}"))
+}
+
+fn parse_size(value: &str) -> Result<usize, String> {
+    match value.parse::<usize>() {
+        Ok(size) if size > 0 => Ok(size),
+        _ => Err(format!("Dimensão inválida: {value}.\n{USAGE}")),
+    }
+}
+
+/// Calcula o centro dos pontos de controle de todos os objetos, usado como ponto focal (P)
+/// da câmera.
+pub fn calc_center(objects: &[Object]) -> Vec3 {
+    let points: Vec<Vec3> = objects
+        .iter()
+        .flat_map(|object| object.control_points.iter().map(mat4x1_to_vec3))
+        .collect();
+
+    if points.is_empty() {
+        Vec3::zeros()
+    } else {
+        points.iter().sum::<Vec3>() / points.len() as f32
+    }
+}
@@ -0,0 +1,73 @@
+use std::io::{self, BufRead};
+
+use crate::import::{invalid_data, parse_point, ControlNet};
+use crate::types::Mat4x1;
+
+/// Lê uma malha de controle em CSV.
+///
+/// Cada linha contém um ponto `x,y,z` ou `x,y,z,w` (o peso é 1 se omitido), e as linhas da
+/// grade (direção i) são separadas por linhas em branco. Linhas iniciadas por `#` e um
+/// cabeçalho não numérico na primeira linha de dados (após os comentários) são ignorados.
+pub fn read_csv<R: BufRead>(reader: R) -> io::Result<ControlNet> {
+    let mut rows: Vec<Vec<Mat4x1>> = Vec::new();
+    let mut row: Vec<Mat4x1> = Vec::new();
+    let mut first_line = true;
+
+    for (k, line) in reader.lines().enumerate() {
+        let line = line?;
+        let line = line.trim();
+
+        if line.starts_with('#') {
+            continue;
+        }
+        if line.is_empty() {
+            if !row.is_empty() {
+                rows.push(std::mem::take(&mut row));
+            }
+            continue;
+        }
+
+        let values: Vec<&str> = line.split(',').collect();
+
+        // Cabeçalho, como "x,y,z,w"
+        let header = first_line && values[0].trim().parse::<f32>().is_err();
+        first_line = false;
+        if header {
+            continue;
+        }
+
+        row.push(parse_point(&values, k + 1)?);
+    }
+
+    if !row.is_empty() {
+        rows.push(row);
+    }
+
+    if rows.is_empty() {
+        return Err(invalid_data("O arquivo não contém pontos de controle.".to_string()));
+    }
+
+    ControlNet::from_rows(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn header_after_comments() {
+        let csv = "# malha 2 x 2\n# gerada à mão\nx,y,z\n0,0,0\n0,1,0\n\n1,0,0\n1,1,2\n";
+        let net = read_csv(csv.as_bytes()).unwrap();
+
+        assert_eq!((net.ni, net.nj), (1, 1));
+        assert_eq!(net.control_points[3].z, 2.0);
+    }
+
+    #[test]
+    fn header_only_on_the_first_data_line() {
+        let csv = "0,0,0\nx,y,z\n";
+        let error = read_csv(csv.as_bytes()).unwrap_err();
+
+        assert!(error.to_string().contains("Linha 2"), "{error}");
+    }
+}
@@ -0,0 +1,111 @@
+pub mod csv;
+pub mod obj;
+
+use std::fs::File;
+use std::io::{self, BufReader};
+use std::path::Path;
+
+use crate::object::Object;
+use crate::types::{Mat4x1, Vec3};
+use crate::utils::weighted_point;
+
+/// Malha de controle lida de um arquivo, com `(ni + 1) x (nj + 1)` pontos armazenados linha
+/// a linha (índice `i * (nj + 1) + j`).
+#[derive(Clone, Debug)]
+pub struct ControlNet {
+    pub ni: usize,
+    pub nj: usize,
+    pub control_points: Vec<Mat4x1>,
+}
+
+impl ControlNet {
+    /// Monta a malha a partir das linhas da grade, que devem ter o mesmo número de pontos.
+    pub fn from_rows(rows: Vec<Vec<Mat4x1>>) -> io::Result<Self> {
+        let columns = rows.first().map_or(0, Vec::len);
+        if rows.len() < 2 || columns < 2 {
+            return Err(invalid_data("A malha de controle deve ter ao menos 2 x 2 pontos.".to_string()));
+        }
+        if let Some(i) = rows.iter().position(|row| row.len() != columns) {
+            return Err(invalid_data(format!(
+                "A linha {} da malha tem {} pontos, esperados {columns}.",
+                i + 1,
+                rows[i].len(),
+            )));
+        }
+
+        Ok(Self {
+            ni: rows.len() - 1,
+            nj: columns - 1,
+            control_points: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    /// Monta a malha a partir de uma lista de pontos, dividida em linhas de `columns` pontos.
+    pub fn from_points(points: Vec<Mat4x1>, columns: usize) -> io::Result<Self> {
+        if columns == 0 || !points.len().is_multiple_of(columns) {
+            return Err(invalid_data(format!(
+                "{} pontos não formam uma grade com {columns} colunas.",
+                points.len(),
+            )));
+        }
+
+        Self::from_rows(points.chunks(columns).map(<[Mat4x1]>::to_vec).collect())
+    }
+
+    /// Cria uma superfície com a malha de controle e as ordens e resoluções informadas.
+    pub fn to_object(self, ti: usize, tj: usize, resi: usize, resj: usize) -> Result<Object, String> {
+        Object::from_control_points(self.ni, self.nj, ti, tj, resi, resj, self.control_points)
+    }
+}
+
+/// Lê uma malha de controle de um arquivo CSV ou OBJ, conforme a extensão do caminho.
+/// As colunas (nj + 1) dos arquivos OBJ são as informadas ou, se omitidas, deduzidas das faces
+/// (ver `obj::read_obj`). Nos arquivos CSV, cada linha do arquivo já é uma linha da grade.
+pub fn load_control_net(path: &Path, columns: Option<usize>) -> io::Result<ControlNet> {
+    let extension = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .map(|extension| extension.to_ascii_lowercase());
+
+    let reader = || File::open(path).map(BufReader::new);
+
+    match extension.as_deref() {
+        Some("csv") => csv::read_csv(reader()?),
+        Some("obj") => obj::read_obj(reader()?, columns),
+        _ => Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("Formato de malha de controle não suportado: {}", path.display()),
+        )),
+    }
+}
+
+fn invalid_data(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+/// Converte a posição e o peso lidos em um ponto de controle homogêneo, com peso 1 se omitido.
+fn parse_point(values: &[&str], line: usize) -> io::Result<Mat4x1> {
+    if values.len() != 3 && values.len() != 4 {
+        return Err(invalid_data(format!(
+            "Linha {line}: esperados 3 ou 4 valores (x, y, z [, w]), encontrados {}.",
+            values.len(),
+        )));
+    }
+
+    let mut coordinates = [0.0, 0.0, 0.0, 1.0];
+    for (coordinate, value) in coordinates.iter_mut().zip(values) {
+        *coordinate = value
+            .trim()
+            .parse::<f32>()
+            .ok()
+            .filter(|value| value.is_finite())
+            .ok_or_else(|| invalid_data(format!("Linha {line}: valor inválido \"{}\".", value.trim())))?;
+    }
+
+    if coordinates[3] <= 0.0 {
+        return Err(invalid_data(format!("Linha {line}: o peso w deve ser positivo.")));
+    }
+
+    let [x, y, z, w] = coordinates;
+    Ok(weighted_point(&Vec3::new(x, y, z), w))
+}
@@ -0,0 +1,172 @@
+use std::io::{self, BufRead};
+
+use crate::import::{invalid_data, parse_point, ControlNet};
+use crate::types::Mat4x1;
+
+/// Lê uma malha de controle dos vértices (`v x y z [w]`) de um arquivo OBJ, em ordem, linha a
+/// linha. Apenas o primeiro objeto (`o`) do arquivo é lido.
+///
+/// Se `columns` não for informado, o número de colunas da grade é deduzido da primeira face,
+/// que deve ser um quadrilátero da grade (como nos arquivos exportados por `export::obj`).
+pub fn read_obj<R: BufRead>(reader: R, columns: Option<usize>) -> io::Result<ControlNet> {
+    let mut points: Vec<Mat4x1> = Vec::new();
+    let mut first_face: Option<Vec<usize>> = None;
+    let mut objects = 0;
+
+    for (k, line) in reader.lines().enumerate() {
+        let line = line?;
+        let mut tokens = line.split_whitespace();
+
+        match tokens.next() {
+            Some("o") => {
+                objects += 1;
+                if objects > 1 {
+                    break;
+                }
+            }
+            Some("v") => {
+                let values: Vec<&str> = tokens.collect();
+                points.push(parse_point(&values, k + 1)?);
+            }
+            Some("f") if first_face.is_none() => {
+                // Apenas o índice da posição, em "v/vt/vn"
+                let face: Option<Vec<usize>> = tokens
+                    .map(|token| token.split('/').next().and_then(|index| index.parse().ok()))
+                    .collect();
+                first_face = face;
+            }
+            _ => {}
+        }
+    }
+
+    if points.is_empty() {
+        return Err(invalid_data("O arquivo não contém vértices.".to_string()));
+    }
+
+    let columns = match columns {
+        Some(columns) => columns,
+        None => first_face
+            .as_deref()
+            .and_then(calc_columns)
+            .ok_or_else(|| invalid_data(
+                "Não foi possível deduzir as colunas da grade: o arquivo não tem faces quadriláteras. \
+                 Informe o número de colunas.".to_string(),
+            ))?,
+    };
+
+    ControlNet::from_points(points, columns)
+}
+
+/// Deduz o número de colunas de uma face quadrilátera da grade `[a, a + c, a + c + 1, a + 1]`
+/// ou `[a, a + 1, a + c + 1, a + c]`.
+fn calc_columns(face: &[usize]) -> Option<usize> {
+    let &[a, b, _, d] = face else {
+        return None;
+    };
+
+    match (b.checked_sub(a)?, d.checked_sub(a)?) {
+        (columns, 1) | (1, columns) if columns > 1 => Some(columns),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::obj::write_obj;
+    use crate::import::load_control_net;
+    use crate::generator::Generator;
+    use crate::object::{DiagonalSplit, Object};
+    use crate::types::Vec3;
+    use crate::utils::mat4x1_to_vec3;
+
+    fn write(objects: &[Object]) -> Vec<u8> {
+        let mut buffer = Vec::new();
+        write_obj(&mut buffer, objects).unwrap();
+        buffer
+    }
+
+    /// Verifica que a malha lida tem a grade e as posições dos vértices do objeto, com peso 1.
+    fn assert_net_matches_mesh(net: &ControlNet, object: &Object) {
+        let (resi, resj) = object.get_resolution();
+        assert_eq!((net.ni + 1, net.nj + 1), (resi, resj));
+        assert_eq!(net.control_points.len(), object.get_vertices().len());
+
+        for (point, vertex) in net.control_points.iter().zip(object.get_vertices()) {
+            assert_eq!(mat4x1_to_vec3(point), mat4x1_to_vec3(vertex));
+            assert_eq!(point.w, 1.0);
+        }
+    }
+
+    #[test]
+    fn reads_back_written_meshes() {
+        let generator = Generator::Random { seed: 9, amplitude: 6.0 };
+        let object = Object::from_generator(&generator, 5, 4, 3, 3, 7, 5).unwrap();
+
+        // Colunas deduzidas da primeira face quadrilátera
+        let net = read_obj(write(std::slice::from_ref(&object)).as_slice(), None).unwrap();
+        assert_net_matches_mesh(&net, &object);
+    }
+
+    #[test]
+    fn reads_only_the_first_object() {
+        let generator = Generator::Random { seed: 9, amplitude: 6.0 };
+        let first = Object::from_generator(&generator, 4, 4, 3, 3, 6, 4).unwrap();
+        let second = Object::from_generator(&generator, 3, 3, 2, 2, 5, 5).unwrap();
+        let objects = [first, second];
+
+        let net = read_obj(write(&objects).as_slice(), None).unwrap();
+        assert_net_matches_mesh(&net, &objects[0]);
+    }
+
+    #[test]
+    fn triangulated_meshes_need_the_columns() {
+        let generator = Generator::Random { seed: 9, amplitude: 6.0 };
+        let mut object = Object::from_generator(&generator, 4, 4, 3, 3, 6, 4).unwrap();
+        object.set_triangulation(Some(DiagonalSplit::Shortest));
+        let obj = write(std::slice::from_ref(&object));
+
+        // A primeira face é um triângulo, de onde as colunas não podem ser deduzidas
+        assert!(read_obj(obj.as_slice(), None).is_err());
+        assert_net_matches_mesh(&read_obj(obj.as_slice(), Some(4)).unwrap(), &object);
+    }
+
+    #[test]
+    fn reads_vertex_only_lattices() {
+        // Grade 2 x 3 apenas com vértices, sem faces
+        let obj = "v 0 0 0\nv 1 0 1\nv 2 0 0\nv 0 1 1\nv 1 1 2\nv 2 1 1\n";
+        assert!(read_obj(obj.as_bytes(), None).is_err());
+
+        let path = std::env::temp_dir().join(format!("aleluia-grade-{}.obj", std::process::id()));
+        std::fs::write(&path, obj).unwrap();
+        let net = load_control_net(&path, Some(3));
+        std::fs::remove_file(&path).unwrap();
+
+        let net = net.unwrap();
+        assert_eq!((net.ni, net.nj), (1, 2));
+        assert_eq!(mat4x1_to_vec3(&net.control_points[4]), Vec3::new(1.0, 1.0, 2.0));
+        assert!(net.to_object(2, 3, 4, 4).is_ok());
+    }
+
+    #[test]
+    fn reads_weighted_vertices() {
+        let obj = "o pesos\nv 0 0 0\nv 1 0 0 2\nv 0 1 0 0.5\nv 1 1 3 4\nf 1 3 4 2\n";
+        let net = read_obj(obj.as_bytes(), None).unwrap();
+
+        assert_eq!((net.ni, net.nj), (1, 1));
+        let weights: Vec<f32> = net.control_points.iter().map(|point| point.w).collect();
+        assert_eq!(weights, [1.0, 2.0, 0.5, 4.0]);
+        // Pontos homogêneos (w·x, w·y, w·z, w)
+        assert_eq!(net.control_points[1], Mat4x1::new(2.0, 0.0, 0.0, 2.0));
+        assert_eq!(mat4x1_to_vec3(&net.control_points[3]), Vec3::new(1.0, 1.0, 3.0));
+
+        let object = net.to_object(2, 2, 3, 3).unwrap();
+        assert_eq!(object.get_weight(1), 2.0);
+    }
+
+    #[test]
+    fn rejects_non_positive_weights() {
+        let error = read_obj("v 0 0 0 0\n".as_bytes(), Some(1)).unwrap_err();
+        assert!(error.to_string().contains("Linha 1"), "{error}");
+    }
+}
@@ -0,0 +1,20 @@
+//! Modelagem e visualização de superfícies B-spline.
+//!
+//! A biblioteca expõe a geometria das superfícies (`object`, `basis`, `generator`, `conic`,
+//! `types` e `utils`), a câmera (`camera`) e o pipeline de rasterização por software
+//! (`render`), além da renderização fora da tela (`headless`), do formato de arquivo de cena
+//! (`scene`), da exportação das malhas (`export`) e da importação de malhas de controle
+//! (`import`). A interface gráfica fica no binário.
+
+pub mod basis;
+pub mod camera;
+pub mod conic;
+pub mod export;
+pub mod generator;
+pub mod headless;
+pub mod import;
+pub mod object;
+pub mod render;
+pub mod scene;
+pub mod types;
+pub mod utils;
@@ -1,19 +1,37 @@
+#[cfg(feature = "gui")]
 mod app;
-mod camera;
+#[cfg(feature = "gui")]
 mod constants;
-mod object;
-mod render;
-mod types;
-mod utils;
 
-use eframe::{NativeOptions, Result, run_native};
-use eframe::egui::ViewportBuilder;
-use crate::constants::{GUI_HEIGHT, GUI_WIDTH};
-use crate::app::MyApp;
+use std::process::ExitCode;
 
-fn main() -> Result {
+use aleluia::headless;
+
+fn main() -> ExitCode {
     env_logger::init();
 
+    let args: Vec<String> = std::env::args().collect();
+    if args.get(1).map(String::as_str) == Some("render") {
+        return match headless::run(&args[2..]) {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(error) => {
+                eprintln!("{error}");
+                ExitCode::FAILURE
+            }
+        };
+    }
+
+    run_gui()
+}
+
+/// Abre a janela da interface gráfica.
+#[cfg(feature = "gui")]
+fn run_gui() -> ExitCode {
+    use eframe::{NativeOptions, run_native};
+    use eframe::egui::ViewportBuilder;
+    use crate::constants::{GUI_HEIGHT, GUI_WIDTH};
+    use crate::app::MyApp;
+
     let title = "Aleluia";
 
     let options = NativeOptions {
@@ -22,9 +40,25 @@ fn main() -> Result {
         ..Default::default()
     };
 
-    run_native(
+    let result = run_native(
         title,
         options,
         Box::new(|_cc| Ok(Box::<MyApp>::default())),
-    )
-}
\ No newline at end of file
+    );
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("{error}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Sem a interface gráfica, apenas o subcomando `render` está disponível.
+#[cfg(not(feature = "gui"))]
+fn run_gui() -> ExitCode {
+    eprintln!("Compilado sem a interface gráfica (feature `gui`). Use o subcomando `render`.");
+    eprintln!("{}", headless::USAGE);
+    ExitCode::FAILURE
+}
@@ -1,6 +1,73 @@
-use rand::Rng;
-use std::sync::{Arc, Mutex};
-use crate::types::Mat4x1;
+use std::ops::Range;
+
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use crate::basis::BasisTable;
+use crate::generator::Generator;
+use crate::render::Render;
+use crate::types::{Mat4x1, Vec3};
+use crate::utils::{mat4x1_to_vec3, weighted_point, weld_tolerance};
+
+/// Estratégia de escolha da diagonal na divisão das faces quadriláteras em triângulos.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum DiagonalSplit {
+    /// Sempre divide pela diagonal que liga o primeiro ao terceiro vértice.
+    Fixed,
+    /// Divide pela menor das duas diagonais.
+    Shortest,
+    /// Alterna a diagonal em padrão de tabuleiro de xadrez.
+    Alternating,
+}
+
+impl DiagonalSplit {
+    pub const ALL: [DiagonalSplit; 3] = [
+        DiagonalSplit::Fixed,
+        DiagonalSplit::Shortest,
+        DiagonalSplit::Alternating,
+    ];
+
+    /// Nome da estratégia exibido na interface.
+    pub fn name(&self) -> &'static str {
+        match self {
+            DiagonalSplit::Fixed => "Fixa",
+            DiagonalSplit::Shortest => "Menor diagonal",
+            DiagonalSplit::Alternating => "Alternada",
+        }
+    }
+}
+
+/// Vetores de nós pré-definidos.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum KnotPreset {
+    /// Uniforme aberto: nós com multiplicidade igual à ordem nas extremidades, para que a
+    /// superfície passe pelos pontos de controle da borda.
+    OpenUniform,
+    /// Uniforme periódico: nós igualmente espaçados, sem repetição.
+    PeriodicUniform,
+    /// Nós pela média dos parâmetros proporcionais à distância entre os pontos de controle.
+    ChordLength,
+    /// Como `ChordLength`, com a raiz quadrada das distâncias.
+    Centripetal,
+}
+
+impl KnotPreset {
+    pub const ALL: [KnotPreset; 4] = [
+        KnotPreset::OpenUniform,
+        KnotPreset::PeriodicUniform,
+        KnotPreset::ChordLength,
+        KnotPreset::Centripetal,
+    ];
+
+    /// Nome do vetor de nós exibido na interface.
+    pub fn name(&self) -> &'static str {
+        match self {
+            KnotPreset::OpenUniform => "Uniforme aberto",
+            KnotPreset::PeriodicUniform => "Uniforme periódico",
+            KnotPreset::ChordLength => "Comprimento de corda",
+            KnotPreset::Centripetal => "Centrípeto",
+        }
+    }
+}
 
 /// Estrutura para armazenar uma superfície BSpline.
 #[derive(Debug)]
@@ -23,36 +90,72 @@ pub struct Object {
     /// Nós (knots) na direção j.
     knots_j: Vec<f32>,
 
-    /// Pontos de controle.
+    /// Pontos de controle em coordenadas homogêneas (w·x, w·y, w·z, w), em que w é o peso.
     pub control_points: Vec<Mat4x1>,
 
+    /// Funções de base não nulas de cada linha (i) da malha, mantidas para a atualização local.
+    basis_i: BasisTable,
+    /// Funções de base não nulas de cada coluna (j) da malha.
+    basis_j: BasisTable,
+
     /// Lista de vertices da malha interpolada.
     vertices: Vec<Mat4x1>,
+    /// Vetores normais dos vértices, médias das normais das faces (ou triângulos) adjacentes.
+    normals: Vec<Vec3>,
     /// Lista de faces da malha interpolada.
     faces: Vec<[usize; 4]>,
+
+    /// Estratégia de triangulação das faces. Se `None`, os triângulos não são gerados.
+    triangulation: Option<DiagonalSplit>,
+    /// Lista de triângulos da malha interpolada.
+    triangles: Vec<[usize; 3]>,
 }
 
 impl Object {
-    pub fn new(
+    /// Cria uma superfície com os pontos de controle produzidos pelo gerador.
+    pub fn from_generator(
+        generator: &Generator,
         ni: usize,
         nj: usize,
         ti: usize,
         tj: usize,
         resi: usize,
         resj: usize,
-    ) -> Self {
-        let mut rng = rand::thread_rng();
+    ) -> Result<Self, String> {
+        Self::from_control_points(ni, nj, ti, tj, resi, resj, generator.control_points(ni, nj))
+    }
 
-        let mut control_points: Vec<Mat4x1> = Vec::with_capacity((ni + 1) * (nj + 1));
-        for i in 0..=ni {
-            for j in 0..=nj {
-                control_points.push(Mat4x1::new(
-                    i as f32,
-                    j as f32,
-                    rng.gen_range(0.0..10.0),
-                    1.0,
-                ));
-            }
+    /// Cria uma superfície a partir de uma grade de `(ni + 1) x (nj + 1)` pontos de controle
+    /// homogêneos, armazenados linha a linha (índice `i * (nj + 1) + j`), com nós uniformes
+    /// abertos.
+    pub fn from_control_points(
+        ni: usize,
+        nj: usize,
+        ti: usize,
+        tj: usize,
+        resi: usize,
+        resj: usize,
+        control_points: Vec<Mat4x1>,
+    ) -> Result<Self, String> {
+        if control_points.len() != (ni + 1) * (nj + 1) {
+            return Err(format!(
+                "Esperados {} pontos de controle ({} x {}), encontrados {}.",
+                (ni + 1) * (nj + 1),
+                ni + 1,
+                nj + 1,
+                control_points.len(),
+            ));
+        }
+        if control_points.iter().any(|point| point.w <= 0.0 || !point.w.is_finite()) {
+            return Err("Os pesos dos pontos de controle devem ser positivos.".to_string());
+        }
+        if ti < 2 || ti > ni + 1 || tj < 2 || tj > nj + 1 {
+            return Err(format!(
+                "Ordens inválidas ({ti}, {tj}): devem estar entre 2 e o número de pontos de controle em cada direção.",
+            ));
+        }
+        if resi < 2 || resj < 2 {
+            return Err(format!("Resoluções inválidas ({resi}, {resj}): devem ser ao menos 2."));
         }
 
         let knots_i: Vec<f32> = Self::spline_knots(ni, ti);
@@ -67,140 +170,315 @@ impl Object {
             resj,
             control_points,
 
+            basis_i: Self::calc_basis_table(&knots_i, ni, ti, resi),
+            basis_j: Self::calc_basis_table(&knots_j, nj, tj, resj),
+
             knots_i,
             knots_j,
 
             vertices: vec![Mat4x1::zeros(); resi * resj],
+            normals: Vec::new(),
             faces: Vec::with_capacity((resi - 1) * (resj - 1)),
+
+            triangulation: None,
+            triangles: Vec::new(),
         };
 
         obj.gen_mesh();
 
-        obj
+        Ok(obj)
     }
 
     /// Gera a malha da superfície.
     pub fn gen_mesh(&mut self) {
-        // Zera os vértices iniciais
-        for ipt in &mut self.vertices {
-            *ipt = Mat4x1::new(0.0, 0.0, 0.0, 1.0);
-        }
+        // Funções de base não nulas de cada linha (i) e coluna (j) da malha, calculadas uma vez
+        self.basis_i = Self::calc_basis_table(&self.knots_i, self.ni, self.ti, self.resi);
+        self.basis_j = Self::calc_basis_table(&self.knots_j, self.nj, self.tj, self.resj);
 
-        // Cálculo dos incrementos
-        let increment_i = (self.ni as f32 - self.ti as f32 + 2.0) / self.resi as f32;
-        let increment_j = (self.nj as f32 - self.tj as f32 + 2.0) / self.resj as f32;
+        let (basis_i, basis_j) = (&self.basis_i, &self.basis_j);
+        let control_points = &self.control_points;
+        let nj = self.nj;
 
-        // Vamos definir um número de threads (por exemplo, 4)
-        let n_threads = 4;
+        // Cada linha (i) de vértices é avaliada em paralelo, diretamente no vetor de vértices
+        self.vertices
+            .par_chunks_mut(self.resj)
+            .enumerate()
+            .for_each(|(i, row)| {
+                for (j, vertex) in row.iter_mut().enumerate() {
+                    *vertex = Self::calc_vertex(control_points, nj, basis_i.get(i), basis_j.get(j));
+                }
+            });
 
-        // Dividimos as linhas (i) em blocos
-        let chunk_size = (self.resi + n_threads - 1) / n_threads;
+        // Por fim, geramos as faces
+        let (resi, resj) = (self.resi, self.resj);
+        self.faces.clear();
+        for i in 0..resi - 1 {
+            for j in 0..resj - 1 {
+                // Sentido anti-horário visto de +z, para que a normal aponte para +z
+                self.faces.push([
+                    i * resj + j,
+                    (i + 1) * resj + j,
+                    (i + 1) * resj + (j + 1),
+                    i * resj + (j + 1),
+                ]);
+            }
+        }
+
+        self.gen_triangles();
+        self.gen_normals();
+    }
 
-        // Para facilitar o acesso concorrente, usamos Arc para ler e escrever de forma segura
-        // - knots_i e knots_j, control_points são apenas lidos (podem ser compartilhados sem Mutex).
-        // - vertices precisa de Mutex para escrita simultânea.
-        let arc_knots_i = Arc::new(self.knots_i.clone());
-        let arc_knots_j = Arc::new(self.knots_j.clone());
-        let arc_control_points = Arc::new(self.control_points.clone());
+    /// Altera a posição do ponto de controle, mantendo o seu peso, e atualiza a malha.
+    pub fn set_control_point(&mut self, index: usize, position: &Vec3) {
+        let point = &mut self.control_points[index];
+        *point = weighted_point(position, point.w);
+        self.update_control_point(index);
+    }
 
-        // Precisamos de Mutex para poder escrever em 'vertices' paralelamente
-        let arc_vertices = Arc::new(Mutex::new(vec![Mat4x1::zeros(); self.resi * self.resj]));
+    /// Atualiza a malha após a alteração de um único ponto de controle.
+    ///
+    /// Apenas os vértices no suporte local do ponto (ti x tj intervalos de nós) são reavaliados.
+    /// Em seguida, são refeitos os triângulos das faces que tocam esses vértices e as normais
+    /// dos vértices dessas faces.
+    pub fn update_control_point(&mut self, index: usize) {
+        let rows = self.basis_i.support(index / (self.nj + 1));
+        let columns = self.basis_j.support(index % (self.nj + 1));
+        if rows.is_empty() || columns.is_empty() {
+            return;
+        }
 
-        // Clonamos valores necessários (por simplicidade, eles podem ser copiados ou clonados)
-        let ni = self.ni;
+        let (basis_i, basis_j) = (&self.basis_i, &self.basis_j);
+        let control_points = &self.control_points;
         let nj = self.nj;
-        let ti = self.ti;
-        let tj = self.tj;
-        let resi = self.resi;
         let resj = self.resj;
 
-        // Vetor de handles de thread
-        let mut handles = Vec::with_capacity(n_threads);
+        self.vertices[rows.start * resj..rows.end * resj]
+            .par_chunks_mut(resj)
+            .zip(rows.clone())
+            .for_each(|(row, i)| {
+                for j in columns.clone() {
+                    row[j] = Self::calc_vertex(control_points, nj, basis_i.get(i), basis_j.get(j));
+                }
+            });
+
+        // Faces com algum vértice alterado e vértices dessas faces
+        let face_rows = rows.start.saturating_sub(1)..rows.end.min(self.resi - 1);
+        let face_columns = columns.start.saturating_sub(1)..columns.end.min(self.resj - 1);
+        let normal_rows = face_rows.start..face_rows.end + 1;
+        let normal_columns = face_columns.start..face_columns.end + 1;
+
+        if let Some(triangulation) = self.triangulation {
+            for fi in face_rows {
+                for fj in face_columns.clone() {
+                    let k = fi * (self.resj - 1) + fj;
+                    let [first, second] = self.calc_face_triangles(k, triangulation);
+                    self.triangles[2 * k] = first;
+                    self.triangles[2 * k + 1] = second;
+                }
+            }
+        }
+
+        let touches_boundary = normal_rows.start == 0
+            || normal_rows.end == self.resi
+            || normal_columns.start == 0
+            || normal_columns.end == self.resj;
 
-        for t in 0..n_threads {
-            // Definimos o range de i para cada thread
-            let start_i = t * chunk_size;
-            let end_i = (start_i + chunk_size).min(resi);
+        for i in normal_rows {
+            for j in normal_columns.clone() {
+                let normal = self.calc_vertex_normal(i, j);
+                self.normals[i * self.resj + j] = normal;
+            }
+        }
 
-            // Clonamos as referências compartilhadas
-            let knots_i = Arc::clone(&arc_knots_i);
-            let knots_j = Arc::clone(&arc_knots_j);
-            let control_points = Arc::clone(&arc_control_points);
-            let vertices = Arc::clone(&arc_vertices);
+        if touches_boundary {
+            self.weld_seam_normals();
+        }
+    }
 
-            // Spawn da thread
-            let handle = std::thread::spawn(move || {
-                // Vetor local para armazenar o resultado parcial
-                let mut local_vertices =
-                    vec![Mat4x1::new(0.0, 0.0, 0.0, 1.0); (end_i - start_i) * resj];
+    /// Avalia um vértice da superfície a partir das funções de base não nulas em i e em j,
+    /// somando as contribuições dos ti x tj pontos de controle correspondentes.
+    #[inline(always)]
+    fn calc_vertex(
+        control_points: &[Mat4x1],
+        nj: usize,
+        (first_i, values_i): (usize, &[f32]),
+        (first_j, values_j): (usize, &[f32]),
+    ) -> Mat4x1 {
+        let mut vertex = Mat4x1::zeros();
+        for (a, &value_i) in values_i.iter().enumerate() {
+            let row = (first_i + a) * (nj + 1) + first_j;
+            for (b, &value_j) in values_j.iter().enumerate() {
+                vertex += control_points[row + b] * (value_i * value_j);
+            }
+        }
 
-                // Iniciamos o intervalo de i de acordo com start_i
-                let mut interval_i = start_i as f32 * increment_i;
+        // Avaliação racional (NURBS): divide pela soma ponderada dos pesos
+        if vertex.w != 0.0 {
+            vertex /= vertex.w;
+        }
+        vertex
+    }
 
-                for i in start_i..end_i {
-                    let mut interval_j = 0.0;
+    /// Tabela das funções de base nos `res` parâmetros distribuídos uniformemente no domínio dos
+    /// nós `[u(t - 1), u(n + 1)]`, incluindo os extremos.
+    fn calc_basis_table(knots: &[f32], n: usize, t: usize, res: usize) -> BasisTable {
+        let start = knots[t - 1];
+        let end = knots[n + 1];
+        let parameters: Vec<f32> = (0..res)
+            .map(|k| start + (end - start) * k as f32 / (res - 1) as f32)
+            .collect();
 
-                    for j in 0..resj {
-                        let local_idx = (i - start_i) * resj + j;
+        BasisTable::new(knots, n, t, &parameters)
+    }
 
-                        // Soma as contribuições de cada ponto de controle
-                        for ki in 0..=ni {
-                            for kj in 0..=nj {
-                                let bi = Self::spline_blend(ki, ti, &knots_i, interval_i);
-                                let bj = Self::spline_blend(kj, tj, &knots_j, interval_j);
+    /// Gera os triângulos da malha a partir das faces, conforme a estratégia de triangulação.
+    fn gen_triangles(&mut self) {
+        self.triangles.clear();
 
-                                let blend = bi * bj;
-                                let cp_idx = ki * (nj + 1) + kj;
+        let Some(triangulation) = self.triangulation else {
+            return;
+        };
 
-                                local_vertices[local_idx] =
-                                    local_vertices[local_idx] + control_points[cp_idx] * blend;
-                            }
-                        }
-                        interval_j += increment_j;
-                    }
-                    interval_i += increment_i;
-                }
+        self.triangles.reserve(2 * self.faces.len());
+        for k in 0..self.faces.len() {
+            let triangles = self.calc_face_triangles(k, triangulation);
+            self.triangles.extend(triangles);
+        }
+    }
 
-                // Copiamos o resultado parcial para o vetor global
-                {
-                    let mut global_vertices = vertices.lock().unwrap();
-                    for i_local in 0..(end_i - start_i) {
-                        for j_local in 0..resj {
-                            let local_out_idx = i_local * resj + j_local;
-                            let global_out_idx = (start_i + i_local) * resj + j_local;
-                            global_vertices[global_out_idx] = local_vertices[local_out_idx];
-                        }
-                    }
-                }
-            });
-            handles.push(handle);
+    /// Divide a face `k` em dois triângulos, conforme a estratégia de triangulação.
+    fn calc_face_triangles(&self, k: usize, triangulation: DiagonalSplit) -> [[usize; 3]; 2] {
+        let [a, b, c, d] = self.faces[k];
+        let columns = self.resj - 1;
+
+        let split_ac = match triangulation {
+            DiagonalSplit::Fixed => true,
+            DiagonalSplit::Shortest => {
+                let ac = mat4x1_to_vec3(&self.vertices[c]) - mat4x1_to_vec3(&self.vertices[a]);
+                let bd = mat4x1_to_vec3(&self.vertices[d]) - mat4x1_to_vec3(&self.vertices[b]);
+                ac.norm_squared() <= bd.norm_squared()
+            }
+            DiagonalSplit::Alternating => (k / columns + k % columns).is_multiple_of(2),
+        };
+
+        // Mantém a orientação (sentido) dos vértices da face original
+        if split_ac {
+            [[a, b, c], [a, c, d]]
+        } else {
+            [[a, b, d], [b, c, d]]
         }
+    }
+
+    /// Calcula os vetores normais de todos os vértices.
+    fn gen_normals(&mut self) {
+        let resj = self.resj;
+        let normals: Vec<Vec3> = (0..self.vertices.len())
+            .into_par_iter()
+            .map(|vertex| self.calc_vertex_normal(vertex / resj, vertex % resj))
+            .collect();
+        self.normals = normals;
+        self.weld_seam_normals();
+    }
 
-        // Esperamos todas as threads terminarem
-        for handle in handles {
-            handle.join().unwrap();
+    /// Substitui as normais dos vértices coincidentes da borda, como os da costura e dos polos
+    /// das superfícies fechadas, pela média das suas normais. Cada cópia de um vértice da
+    /// costura só enxerga as faces de um dos lados; sem a média, o sombreamento Gouraud e Phong
+    /// marcaria a costura.
+    ///
+    /// As normais de toda a borda são antes recalculadas sem a média, pois as armazenadas podem
+    /// ser médias de uma costura que deixou de existir (por exemplo, após mover um ponto de
+    /// controle da costura).
+    fn weld_seam_normals(&mut self) {
+        let (resi, resj) = (self.resi, self.resj);
+        let mut boundary: Vec<usize> = (0..resj).chain((resi - 1) * resj..resi * resj).collect();
+        for i in 1..resi - 1 {
+            boundary.push(i * resj);
+            boundary.push(i * resj + resj - 1);
         }
 
-        // Agora podemos recuperar os vértices calculados para dentro de self.vertices
-        {
-            let final_vertices = arc_vertices.lock().unwrap();
-            self.vertices.copy_from_slice(&final_vertices);
+        for &k in &boundary {
+            self.normals[k] = self.calc_vertex_normal(k / resj, k % resj);
         }
 
-        // Por fim, geramos as faces (pode ser paralelizado também, mas aqui está em uma thread só)
-        self.faces.clear();
-        for i in 0..resi - 1 {
-            for j in 0..resj - 1 {
-                self.faces.push([
-                    i * resj + j,
-                    i * resj + (j + 1),
-                    (i + 1) * resj + (j + 1),
-                    (i + 1) * resj + j,
-                ]);
+        let positions: Vec<Vec3> = boundary.iter().map(|&k| mat4x1_to_vec3(&self.vertices[k])).collect();
+        let tolerance = weld_tolerance(&positions);
+
+        let mut welded = vec![false; boundary.len()];
+        for a in 0..boundary.len() {
+            if welded[a] {
+                continue;
+            }
+            let group: Vec<usize> = (a..boundary.len())
+                .filter(|&b| !welded[b] && (positions[b] - positions[a]).norm() <= tolerance)
+                .collect();
+            if group.len() < 2 {
+                continue;
+            }
+
+            let normal: Vec3 = group.iter().map(|&b| self.normals[boundary[b]]).sum();
+            let normal = normal.try_normalize(f32::EPSILON).unwrap_or_else(Vec3::zeros);
+            for &b in &group {
+                welded[b] = true;
+                self.normals[boundary[b]] = normal;
             }
         }
     }
 
+    /// Calcula o vetor normal do vértice (i, j) como a média das normais das faces adjacentes,
+    /// ou dos triângulos adjacentes se a triangulação estiver habilitada. As faces são somadas
+    /// na mesma ordem de `Render::calc_vertex_normals`; fora dos vértices coincidentes da borda
+    /// (ver `weld_seam_normals`), as normais armazenadas são idênticas às calculadas por ele.
+    fn calc_vertex_normal(&self, i: usize, j: usize) -> Vec3 {
+        let vertex = i * self.resj + j;
+        let columns = self.resj - 1;
+        let mut normal = Vec3::zeros();
+
+        for fi in Self::adjacent_faces(i, self.resi) {
+            for fj in Self::adjacent_faces(j, self.resj) {
+                let k = fi * columns + fj;
+                match self.triangulation {
+                    Some(_) => {
+                        for triangle in &self.triangles[2 * k..2 * k + 2] {
+                            if triangle.contains(&vertex) {
+                                normal += Self::valid_face_normal(&self.vertices, triangle);
+                            }
+                        }
+                    }
+                    None => normal += Self::valid_face_normal(&self.vertices, &self.faces[k]),
+                }
+            }
+        }
+
+        normal.try_normalize(f32::EPSILON).unwrap_or_else(Vec3::zeros)
+    }
+
+    /// Índices, em uma direção, das faces que contêm o vértice `k` de uma malha com `res`
+    /// vértices nessa direção.
+    fn adjacent_faces(k: usize, res: usize) -> Range<usize> {
+        k.saturating_sub(1)..k.min(res - 2) + 1
+    }
+
+    /// Normal da face, ou zero se ela for degenerada.
+    fn valid_face_normal(vertices: &[Mat4x1], face: &[usize]) -> Vec3 {
+        let normal: Vec3 = Render::calc_face_normal(vertices, face);
+        if normal.iter().any(|c| c.is_nan()) {
+            Vec3::zeros()
+        } else {
+            normal
+        }
+    }
+
+    /// Define a estratégia de triangulação e regenera os triângulos.
+    pub fn set_triangulation(&mut self, triangulation: Option<DiagonalSplit>) {
+        self.triangulation = triangulation;
+        self.gen_triangles();
+        self.gen_normals();
+    }
+
+    pub fn get_triangulation(&self) -> Option<DiagonalSplit> {
+        self.triangulation
+    }
+
     /// Gera o vetor de nós (knots).
     fn spline_knots(n: usize, t: usize) -> Vec<f32> {
         let mut knots = Vec::with_capacity(n + t + 1);
@@ -216,27 +494,187 @@ impl Object {
         knots
     }
 
-    /// Função de base da spline recursiva.
-    fn spline_blend(k: usize, t: usize, u: &[f32], v: f32) -> f32 {
-        if t == 1 {
-            if u[k] <= v && v < u[k + 1] {
-                1.0
-            } else {
-                0.0
+    /// Retorna as quantidades de pontos de controle (ni, nj), contadas a partir de zero.
+    pub fn get_size(&self) -> (usize, usize) {
+        (self.ni, self.nj)
+    }
+
+    /// Retorna as ordens da spline (ti, tj).
+    pub fn get_orders(&self) -> (usize, usize) {
+        (self.ti, self.tj)
+    }
+
+    /// Retorna as resoluções da malha (resi, resj).
+    pub fn get_resolution(&self) -> (usize, usize) {
+        (self.resi, self.resj)
+    }
+
+    /// Calcula as coordenadas paramétricas (u, v) de cada vértice da malha, normalizadas para
+    /// o intervalo [0, 1] do domínio dos nós, na mesma ordem de `get_vertices`.
+    pub fn get_uvs(&self) -> Vec<[f32; 2]> {
+        let mut uvs = Vec::with_capacity(self.resi * self.resj);
+        for i in 0..self.resi {
+            for j in 0..self.resj {
+                uvs.push([
+                    i as f32 / (self.resi - 1) as f32,
+                    j as f32 / (self.resj - 1) as f32,
+                ]);
             }
-        } else {
-            let mut value = 0.0;
-            let denom1 = u[k + t - 1] - u[k];
-            let denom2 = u[k + t] - u[k + 1];
+        }
+        uvs
+    }
+
+    /// Retorna o peso do ponto de controle.
+    pub fn get_weight(&self, index: usize) -> f32 {
+        self.control_points[index].w
+    }
+
+    /// Altera o peso do ponto de controle, mantendo a sua posição, e atualiza a malha.
+    pub fn set_weight(&mut self, index: usize, weight: f32) -> Result<(), String> {
+        if weight <= 0.0 || !weight.is_finite() {
+            return Err(format!("Peso inválido: {weight}. Deve ser positivo."));
+        }
 
-            if denom1 != 0.0 {
-                value += ((v - u[k]) / denom1) * Self::spline_blend(k, t - 1, u, v);
+        let point = &mut self.control_points[index];
+        *point = weighted_point(&mat4x1_to_vec3(point), weight);
+        self.update_control_point(index);
+
+        Ok(())
+    }
+
+    /// Retorna slice imutável para os nós na direção i
+    pub fn get_knots_i(&self) -> &[f32] {
+        &self.knots_i
+    }
+
+    /// Retorna slice imutável para os nós na direção j
+    pub fn get_knots_j(&self) -> &[f32] {
+        &self.knots_j
+    }
+
+    /// Define os vetores de nós e regenera a malha.
+    ///
+    /// Cada vetor deve ter `n + t + 1` valores não decrescentes e um domínio não vazio
+    /// (`u(t - 1) < u(n + 1)`). Caso contrário, os nós atuais são mantidos.
+    pub fn set_knots(&mut self, knots_i: Vec<f32>, knots_j: Vec<f32>) -> Result<(), String> {
+        Self::validate_knots(&knots_i, self.ni, self.ti)?;
+        Self::validate_knots(&knots_j, self.nj, self.tj)?;
+
+        self.knots_i = knots_i;
+        self.knots_j = knots_j;
+        self.gen_mesh();
+
+        Ok(())
+    }
+
+    /// Calcula os vetores de nós (i, j) do tipo pré-definido para a malha de controle atual.
+    pub fn calc_knot_preset(&self, preset: KnotPreset) -> (Vec<f32>, Vec<f32>) {
+        let stride_i = self.nj + 1;
+        let knots_i = match preset {
+            KnotPreset::OpenUniform => Self::spline_knots(self.ni, self.ti),
+            KnotPreset::PeriodicUniform => Self::periodic_knots(self.ni, self.ti),
+            KnotPreset::ChordLength | KnotPreset::Centripetal => {
+                let parameters = self.calc_parameters(self.ni, self.nj + 1, stride_i, 1, preset);
+                Self::averaged_knots(&parameters, self.ni, self.ti)
             }
-            if denom2 != 0.0 {
-                value += ((u[k + t] - v) / denom2) * Self::spline_blend(k + 1, t - 1, u, v);
+        };
+        let knots_j = match preset {
+            KnotPreset::OpenUniform => Self::spline_knots(self.nj, self.tj),
+            KnotPreset::PeriodicUniform => Self::periodic_knots(self.nj, self.tj),
+            KnotPreset::ChordLength | KnotPreset::Centripetal => {
+                let parameters = self.calc_parameters(self.nj, self.ni + 1, 1, stride_i, preset);
+                Self::averaged_knots(&parameters, self.nj, self.tj)
             }
-            value
+        };
+        (knots_i, knots_j)
+    }
+
+    /// Gera o vetor de nós uniforme periódico, `0, 1, ..., n + t`.
+    fn periodic_knots(n: usize, t: usize) -> Vec<f32> {
+        (0..=(n + t)).map(|k| k as f32).collect()
+    }
+
+    /// Calcula os parâmetros `0 = u(0) <= ... <= u(n) = 1` dos pontos de controle ao longo de
+    /// uma direção, pela média dos parâmetros de cada uma das `lines` linhas da grade.
+    ///
+    /// O ponto k da linha l está no índice `l * line_stride + k * stride`.
+    fn calc_parameters(
+        &self,
+        n: usize,
+        lines: usize,
+        stride: usize,
+        line_stride: usize,
+        preset: KnotPreset,
+    ) -> Vec<f32> {
+        let mut parameters = vec![0.0; n + 1];
+        let mut count = 0;
+
+        for l in 0..lines {
+            let point = |k: usize| mat4x1_to_vec3(&self.control_points[l * line_stride + k * stride]);
+
+            let distances: Vec<f32> = (1..=n)
+                .map(|k| {
+                    let distance = (point(k) - point(k - 1)).norm();
+                    if preset == KnotPreset::Centripetal { distance.sqrt() } else { distance }
+                })
+                .collect();
+            let total: f32 = distances.iter().sum();
+
+            // Linhas degeneradas (pontos coincidentes) não contribuem
+            if total <= f32::EPSILON {
+                continue;
+            }
+
+            let mut accumulated = 0.0;
+            for (k, distance) in distances.iter().enumerate() {
+                accumulated += distance;
+                parameters[k + 1] += accumulated / total;
+            }
+            count += 1;
+        }
+
+        if count == 0 {
+            return (0..=n).map(|k| k as f32 / n as f32).collect();
         }
+
+        parameters.iter().map(|parameter| parameter / count as f32).collect()
+    }
+
+    /// Gera o vetor de nós aberto pela média de `t - 1` parâmetros consecutivos, no mesmo
+    /// intervalo `[0, n - t + 2]` do vetor uniforme aberto.
+    fn averaged_knots(parameters: &[f32], n: usize, t: usize) -> Vec<f32> {
+        let degree = t - 1;
+        let scale = (n + 2 - t) as f32;
+
+        let mut knots = Vec::with_capacity(n + t + 1);
+        knots.extend(std::iter::repeat_n(0.0, t));
+        for j in 1..=(n + 1 - t) {
+            let average: f32 = parameters[j..j + degree].iter().sum::<f32>() / degree as f32;
+            knots.push(average * scale);
+        }
+        knots.extend(std::iter::repeat_n(scale, t));
+        knots
+    }
+
+    /// Verifica se o vetor de nós é válido para `n + 1` pontos de controle e ordem `t`.
+    fn validate_knots(knots: &[f32], n: usize, t: usize) -> Result<(), String> {
+        if knots.len() != n + t + 1 {
+            return Err(format!(
+                "Esperados {} nós, encontrados {}.",
+                n + t + 1,
+                knots.len(),
+            ));
+        }
+        if knots.iter().any(|knot| !knot.is_finite()) {
+            return Err("Os nós devem ser números finitos.".to_string());
+        }
+        if knots.windows(2).any(|pair| pair[0] > pair[1]) {
+            return Err("Os nós devem estar em ordem não decrescente.".to_string());
+        }
+        if knots[t - 1] >= knots[n + 1] {
+            return Err("O domínio dos nós é vazio.".to_string());
+        }
+        Ok(())
     }
 
     /// Retorna slice imutável para vértices da malha
@@ -244,8 +682,191 @@ impl Object {
         &self.vertices
     }
 
+    /// Retorna slice imutável para os vetores normais dos vértices
+    pub fn get_normals(&self) -> &[Vec3] {
+        &self.normals
+    }
+
     /// Retorna slice imutável para as faces interpolados
     pub fn get_faces(&self) -> &[[usize; 4]] {
         &self.faces
     }
+
+    /// Retorna slice imutável para os triângulos, caso a triangulação esteja habilitada
+    pub fn get_triangles(&self) -> Option<&[[usize; 3]]> {
+        self.triangulation.map(|_| self.triangles.as_slice())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::conic::Conic;
+
+    /// Vértices, normais e triângulos atuais da malha.
+    fn mesh(object: &Object) -> (Vec<Mat4x1>, Vec<Vec3>, Option<Vec<[usize; 3]>>) {
+        (
+            object.get_vertices().to_vec(),
+            object.get_normals().to_vec(),
+            object.get_triangles().map(<[_]>::to_vec),
+        )
+    }
+
+    /// Verifica que a malha atualizada localmente é igual à regenerada por inteiro.
+    fn assert_matches_full_mesh(object: &mut Object, context: &str) {
+        let incremental = mesh(object);
+        object.gen_mesh();
+        let full = mesh(object);
+
+        assert_eq!(incremental.0, full.0, "{context}: vértices");
+        assert_eq!(incremental.1, full.1, "{context}: normais");
+        assert_eq!(incremental.2, full.2, "{context}: triângulos");
+    }
+
+    #[test]
+    fn set_knots_validates_the_vectors() {
+        let generator = Generator::Random { seed: 3, amplitude: 5.0 };
+        let mut object = Object::from_generator(&generator, 4, 3, 3, 2, 9, 7).unwrap();
+        let knots_j = object.get_knots_j().to_vec();
+        let open_uniform = object.get_knots_i().to_vec();
+
+        let invalid = [
+            (vec![0.0, 0.0, 0.0, 1.0, 2.0, 3.0, 3.0], "Esperados 8 nós"),
+            (vec![0.0, 0.0, 0.0, 2.0, 1.0, 3.0, 3.0, 3.0], "não decrescente"),
+            (vec![0.0, 0.0, 0.0, f32::NAN, 2.0, 3.0, 3.0, 3.0], "finitos"),
+            (vec![0.0, 0.0, 1.0, 1.0, 1.0, 1.0, 2.0, 2.0], "domínio"),
+        ];
+        for (knots_i, message) in invalid {
+            let error = object.set_knots(knots_i.clone(), knots_j.clone()).unwrap_err();
+            assert!(error.contains(message), "{knots_i:?}: {error}");
+            // Os nós atuais são mantidos
+            assert_eq!(object.get_knots_i(), open_uniform.as_slice());
+        }
+
+        // Nós não uniformes, mas válidos
+        let non_uniform = vec![0.0, 0.0, 0.0, 0.5, 2.5, 3.0, 3.0, 3.0];
+        object.set_knots(non_uniform.clone(), knots_j).unwrap();
+        assert_eq!(object.get_knots_i(), non_uniform.as_slice());
+    }
+
+    #[test]
+    fn open_uniform_knots_interpolate_the_corners() {
+        let generator = Generator::Random { seed: 5, amplitude: 5.0 };
+        let mut object = Object::from_generator(&generator, 5, 4, 3, 4, 8, 6).unwrap();
+        let (ni, nj) = object.get_size();
+        let (resi, resj) = object.get_resolution();
+        let corners = [
+            (0, 0),
+            (ni * (nj + 1), (resi - 1) * resj),
+            (nj, resj - 1),
+            (ni * (nj + 1) + nj, resi * resj - 1),
+        ];
+        let distance = |object: &Object, (point, vertex): (usize, usize)| {
+            (mat4x1_to_vec3(&object.control_points[point]) - mat4x1_to_vec3(&object.get_vertices()[vertex])).norm()
+        };
+
+        // Depois de nós periódicos, a superfície não passa pelos cantos da malha de controle
+        let (knots_i, knots_j) = object.calc_knot_preset(KnotPreset::PeriodicUniform);
+        object.set_knots(knots_i, knots_j).unwrap();
+        assert!(corners.iter().all(|&corner| distance(&object, corner) > 1e-3));
+
+        let (knots_i, knots_j) = object.calc_knot_preset(KnotPreset::OpenUniform);
+        object.set_knots(knots_i, knots_j).unwrap();
+        for corner in corners {
+            assert!(distance(&object, corner) < 1e-4, "canto {corner:?}");
+        }
+    }
+
+    #[test]
+    fn seam_and_pole_normals_are_shared() {
+        for conic in [Conic::Sphere { radius: 4.0 }, Conic::Torus { major_radius: 5.0, minor_radius: 2.0 }] {
+            let object = conic.to_object(16, 12).unwrap();
+            let (resi, resj) = object.get_resolution();
+            let normals = object.get_normals();
+
+            // A costura angular repete a primeira linha de vértices na última
+            for j in 0..resj {
+                assert_eq!(normals[j], normals[(resi - 1) * resj + j], "{conic:?}, costura em j = {j}");
+            }
+        }
+
+        // No toro, o perfil também é fechado: a primeira coluna se repete na última
+        let torus = Conic::Torus { major_radius: 5.0, minor_radius: 2.0 }.to_object(16, 12).unwrap();
+        let (resi, resj) = torus.get_resolution();
+        for i in 0..resi {
+            assert_eq!(torus.get_normals()[i * resj], torus.get_normals()[i * resj + resj - 1], "toro, i = {i}");
+        }
+
+        // Os polos da esfera são colunas de vértices coincidentes, com uma única normal próxima
+        // do eixo z
+        let sphere = Conic::Sphere { radius: 4.0 }.to_object(16, 12).unwrap();
+        let (resi, resj) = sphere.get_resolution();
+        for (j, z) in [(0, -1.0), (resj - 1, 1.0)] {
+            let pole = sphere.get_normals()[j];
+            assert!(pole.z * z > 0.99, "polo {z}: {pole:?}");
+            for i in 0..resi {
+                assert_eq!(sphere.get_normals()[i * resj + j], pole, "polo {z}, i = {i}");
+            }
+        }
+    }
+
+    #[test]
+    fn local_update_matches_full_mesh() {
+        let generator = Generator::Random { seed: 7, amplitude: 5.0 };
+        let triangulations = [None, Some(DiagonalSplit::Shortest), Some(DiagonalSplit::Alternating)];
+
+        for preset in KnotPreset::ALL {
+            for triangulation in triangulations {
+                // Grade e resoluções diferentes em cada direção, para não mascarar trocas de i e j
+                let mut object = Object::from_generator(&generator, 6, 5, 3, 4, 13, 11).unwrap();
+                let (knots_i, knots_j) = object.calc_knot_preset(preset);
+                object.set_knots(knots_i, knots_j).unwrap();
+                object.set_triangulation(triangulation);
+
+                for index in 0..object.control_points.len() {
+                    let context = format!("{preset:?}, {triangulation:?}, ponto {index}");
+
+                    let position = mat4x1_to_vec3(&object.control_points[index]) + Vec3::new(0.5, -0.25, 3.0);
+                    object.set_control_point(index, &position);
+                    assert_matches_full_mesh(&mut object, &format!("{context}, posição"));
+
+                    let weight = 0.5 + index as f32 * 0.1;
+                    object.set_weight(index, weight).unwrap();
+                    assert_matches_full_mesh(&mut object, &format!("{context}, peso"));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn local_update_matches_full_mesh_on_closed_surfaces() {
+        let conics = [Conic::Sphere { radius: 4.0 }, Conic::Torus { major_radius: 5.0, minor_radius: 2.0 }];
+
+        for conic in conics {
+            for triangulation in [None, Some(DiagonalSplit::Shortest)] {
+                let (ni, nj) = conic.to_object(16, 12).unwrap().get_size();
+
+                // Pontos da costura angular (i = 0), do início do perfil (j = 0, o polo sul da
+                // esfera e a costura do perfil do toro) e um ponto interno
+                let indices = [2, ni * (nj + 1), (ni / 2) * (nj + 1), (ni / 2) * (nj + 1) + nj / 2];
+                for index in indices {
+                    let context = format!("{conic:?}, {triangulation:?}, ponto {index}");
+
+                    // Cada ponto é movido a partir da superfície fechada, com a costura soldada
+                    let mut object = conic.to_object(16, 12).unwrap();
+                    object.set_triangulation(triangulation);
+                    let original = mat4x1_to_vec3(&object.control_points[index]);
+
+                    object.set_control_point(index, &(original + Vec3::new(0.0, 0.0, 1.5)));
+                    let moved = mesh(&object);
+                    assert_matches_full_mesh(&mut object, &format!("{context}, movido"));
+
+                    // Ao voltar à posição original, a costura é soldada de novo
+                    object.set_control_point(index, &original);
+                    assert_matches_full_mesh(&mut object, &format!("{context}, de volta"));
+                    assert_ne!(moved.1, mesh(&object).1, "{context}");
+                }
+            }
+        }
+    }
 }
@@ -0,0 +1,172 @@
+use crate::render::ProjectionType;
+use crate::types::Vec3;
+
+/// Vértice de um polígono durante o recorte, com os atributos a serem interpolados.
+#[derive(Clone, Copy, Debug)]
+pub struct ClipVertex {
+    /// Posição no volume de visão canônico.
+    pub point: Vec3,
+    /// Posição no SRU.
+    pub position: Vec3,
+    /// Intensidade RGB calculada no vértice (sombreamento Gouraud).
+    pub color: Vec3,
+    /// Vetor normal do vértice (sombreamento Phong).
+    pub normal: Vec3,
+    /// Indica se a aresta que sai deste vértice (até o próximo) faz parte de uma aresta da face
+    /// original, e não de um corte feito pelo recorte ao longo de um plano do volume de visão.
+    pub edge: bool,
+}
+
+impl ClipVertex {
+    /// Interpola linearmente todos os atributos entre `self` (t = 0) e `other` (t = 1).
+    #[inline(always)]
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        Self {
+            point: self.point + (other.point - self.point) * t,
+            position: self.position + (other.position - self.position) * t,
+            color: self.color + (other.color - self.color) * t,
+            normal: self.normal + (other.normal - self.normal) * t,
+            edge: self.edge,
+        }
+    }
+}
+
+/// Distâncias com sinal aos planos do volume canônico da projeção perspectiva
+/// (`z <= x <= -z`, `z <= y <= -z`, `-1 <= z <= zmin`). Valores não negativos estão dentro.
+fn perspective_distances(point: &Vec3, zmin: f32) -> [f32; 6] {
+    [
+        point.x - point.z,
+        -point.z - point.x,
+        point.y - point.z,
+        -point.z - point.y,
+        zmin - point.z,
+        point.z + 1.0,
+    ]
+}
+
+/// Distâncias com sinal aos planos do volume canônico da projeção paralela
+/// (`-1 <= x <= 1`, `-1 <= y <= 1`, `-1 <= z <= 0`). Valores não negativos estão dentro.
+fn parallel_distances(point: &Vec3) -> [f32; 6] {
+    [
+        point.x + 1.0,
+        1.0 - point.x,
+        point.y + 1.0,
+        1.0 - point.y,
+        -point.z,
+        point.z + 1.0,
+    ]
+}
+
+/// Recorta o polígono contra o volume de visão canônico pelo algoritmo de Sutherland–Hodgman.
+///
+/// `zmin` é a posição do plano próximo no volume canônico da perspectiva (`-near / far`).
+/// Retorna um polígono vazio se ele estiver completamente fora do volume. As arestas criadas
+/// ao longo dos planos de recorte ficam marcadas com `edge = false`.
+pub fn clip_polygon(
+    polygon: Vec<ClipVertex>,
+    projection_type: &ProjectionType,
+    zmin: f32,
+) -> Vec<ClipVertex> {
+    let distances = |vertex: &ClipVertex| -> [f32; 6] {
+        match projection_type {
+            ProjectionType::Perspective => perspective_distances(&vertex.point, zmin),
+            ProjectionType::Orthographic => parallel_distances(&vertex.point),
+        }
+    };
+
+    // Evita o recorte quando o polígono está inteiramente dentro do volume
+    if polygon.iter().all(|vertex| distances(vertex).iter().all(|&d| d >= 0.0)) {
+        return polygon;
+    }
+
+    let mut output = polygon;
+
+    for plane in 0..6 {
+        if output.is_empty() {
+            break;
+        }
+
+        let input = std::mem::take(&mut output);
+        let mut s = input[input.len() - 1];
+        let mut ds = distances(&s)[plane];
+
+        for p in input {
+            let dp = distances(&p)[plane];
+
+            if dp >= 0.0 {
+                if ds < 0.0 {
+                    // Entrada: o trecho até p ainda é parte da aresta s → p
+                    output.push(s.lerp(&p, ds / (ds - dp)));
+                }
+                output.push(p);
+            } else if ds >= 0.0 {
+                // Saída: a aresta seguinte corre ao longo do plano de recorte
+                output.push(ClipVertex {
+                    edge: false,
+                    ..s.lerp(&p, ds / (ds - dp))
+                });
+            }
+
+            s = p;
+            ds = dp;
+        }
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vertex(x: f32, y: f32, z: f32) -> ClipVertex {
+        ClipVertex {
+            point: Vec3::new(x, y, z),
+            position: Vec3::new(x, y, z),
+            color: Vec3::zeros(),
+            normal: Vec3::zeros(),
+            edge: true,
+        }
+    }
+
+    #[test]
+    fn polygon_inside_keeps_all_edges() {
+        let polygon = vec![
+            vertex(-0.5, -0.5, -0.5),
+            vertex(0.5, -0.5, -0.5),
+            vertex(0.5, 0.5, -0.5),
+            vertex(-0.5, 0.5, -0.5),
+        ];
+
+        let clipped = clip_polygon(polygon, &ProjectionType::Orthographic, 0.0);
+        assert_eq!(clipped.len(), 4);
+        assert!(clipped.iter().all(|vertex| vertex.edge));
+    }
+
+    #[test]
+    fn edges_along_clipping_planes_are_marked() {
+        // Retângulo mais largo que o volume: os lados x = ±2 são cortados em x = ±1
+        let polygon = vec![
+            vertex(-2.0, -0.5, -0.5),
+            vertex(2.0, -0.5, -0.5),
+            vertex(2.0, 0.5, -0.5),
+            vertex(-2.0, 0.5, -0.5),
+        ];
+
+        let clipped = clip_polygon(polygon, &ProjectionType::Orthographic, 0.0);
+        assert_eq!(clipped.len(), 4);
+
+        for (k, v0) in clipped.iter().enumerate() {
+            let v1 = &clipped[(k + 1) % clipped.len()];
+            if v0.edge {
+                // Trechos das arestas originais y = ±0.5
+                assert_eq!(v0.point.y, v1.point.y);
+            } else {
+                // Cortes sobre os planos x = ±1
+                assert_eq!(v0.point.x.abs(), 1.0);
+                assert_eq!(v0.point.x, v1.point.x);
+            }
+        }
+        assert_eq!(clipped.iter().filter(|vertex| !vertex.edge).count(), 2);
+    }
+}
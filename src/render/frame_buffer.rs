@@ -0,0 +1,183 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use crate::render::Viewport;
+
+/// Cor de fundo utilizada ao limpar o buffer de cores.
+pub const BACKGROUND_COLOR: [u8; 3] = [0, 0, 0];
+
+/// Estrutura para armazenar os buffers de cor e profundidade (z-buffer).
+pub struct FrameBuffer {
+    /// Largura em pixels.
+    width: usize,
+    /// Altura em pixels.
+    height: usize,
+    /// Deslocamento horizontal da viewport (umin).
+    u_offset: f32,
+    /// Deslocamento vertical da viewport (vmin).
+    v_offset: f32,
+
+    /// Buffer de cores no formato RGB, com 3 bytes por pixel.
+    color: Vec<u8>,
+    /// Buffer de profundidade. Valores menores estão mais próximos da câmera.
+    depth: Vec<f32>,
+}
+
+impl FrameBuffer {
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            u_offset: 0.0,
+            v_offset: 0.0,
+            color: BACKGROUND_COLOR.repeat(width * height),
+            depth: vec![f32::INFINITY; width * height],
+        }
+    }
+
+    /// Cria um buffer com as dimensões dos intervalos u e v da viewport.
+    pub fn from_viewport(viewport: &Viewport) -> Self {
+        let width = (viewport.umax - viewport.umin).round().max(0.0) as usize + 1;
+        let height = (viewport.vmax - viewport.vmin).round().max(0.0) as usize + 1;
+
+        let mut frame_buffer = Self::new(width, height);
+        frame_buffer.u_offset = viewport.umin;
+        frame_buffer.v_offset = viewport.vmin;
+        frame_buffer
+    }
+
+    /// Redimensiona o buffer, caso necessário, para a viewport e o limpa.
+    pub fn resize_to_viewport(&mut self, viewport: &Viewport) {
+        let frame_buffer = Self::from_viewport(viewport);
+        if frame_buffer.width != self.width || frame_buffer.height != self.height {
+            *self = frame_buffer;
+        } else {
+            self.u_offset = frame_buffer.u_offset;
+            self.v_offset = frame_buffer.v_offset;
+            self.clear();
+        }
+    }
+
+    /// Limpa os buffers de cor e profundidade.
+    pub fn clear(&mut self) {
+        for pixel in self.color.chunks_exact_mut(3) {
+            pixel.copy_from_slice(&BACKGROUND_COLOR);
+        }
+        self.depth.fill(f32::INFINITY);
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Converte coordenadas de tela (SRT) para o índice do pixel no buffer.
+    #[inline(always)]
+    fn index(&self, x: f32, y: f32) -> Option<usize> {
+        let x = (x - self.u_offset).round();
+        let y = (y - self.v_offset).round();
+
+        if x < 0.0 || y < 0.0 || x >= self.width as f32 || y >= self.height as f32 {
+            return None;
+        }
+
+        Some(y as usize * self.width + x as usize)
+    }
+
+    /// Escreve um pixel caso ele passe no teste de profundidade.
+    /// Retorna `true` se o pixel foi escrito.
+    #[inline(always)]
+    pub fn set_pixel(&mut self, x: f32, y: f32, depth: f32, color: [u8; 3]) -> bool {
+        let Some(index) = self.index(x, y) else {
+            return false;
+        };
+
+        if depth < self.depth[index] {
+            self.depth[index] = depth;
+            self.color[3 * index..3 * index + 3].copy_from_slice(&color);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Escreve apenas a profundidade de um pixel, caso ela passe no teste de profundidade.
+    /// Retorna `true` se a profundidade foi escrita.
+    #[inline(always)]
+    pub fn set_depth(&mut self, x: f32, y: f32, depth: f32) -> bool {
+        let Some(index) = self.index(x, y) else {
+            return false;
+        };
+
+        if depth < self.depth[index] {
+            self.depth[index] = depth;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Escreve a cor de um pixel sem realizar o teste de profundidade.
+    /// Retorna `true` se o pixel está dentro do buffer.
+    #[inline(always)]
+    pub fn set_color(&mut self, x: f32, y: f32, color: [u8; 3]) -> bool {
+        let Some(index) = self.index(x, y) else {
+            return false;
+        };
+
+        self.color[3 * index..3 * index + 3].copy_from_slice(&color);
+        true
+    }
+
+    /// Retorna a profundidade armazenada no pixel, se ele estiver dentro do buffer.
+    pub fn get_depth(&self, x: f32, y: f32) -> Option<f32> {
+        self.index(x, y).map(|index| self.depth[index])
+    }
+
+    /// Retorna o buffer de cores no formato RGB.
+    pub fn get_color(&self) -> &[u8] {
+        &self.color
+    }
+
+    /// Escreve o buffer de cores no formato PPM binário (P6).
+    pub fn write_ppm<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        write!(writer, "P6\n{} {}\n255\n", self.width, self.height)?;
+        writer.write_all(&self.color)?;
+        writer.flush()
+    }
+
+    /// Escreve o buffer de cores no formato PNG.
+    pub fn write_png<W: Write>(&self, writer: W) -> io::Result<()> {
+        let mut encoder = png::Encoder::new(writer, self.width as u32, self.height as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&self.color)?;
+        writer.finish()?;
+        Ok(())
+    }
+
+    /// Salva o buffer de cores em um arquivo PNG ou PPM, conforme a extensão do caminho.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let extension = path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .map(|extension| extension.to_ascii_lowercase());
+
+        let writer = || File::create(path).map(BufWriter::new);
+
+        match extension.as_deref() {
+            Some("png") => self.write_png(writer()?),
+            Some("ppm") => self.write_ppm(writer()?),
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("Formato de imagem não suportado: {}", path.display()),
+            )),
+        }
+    }
+}
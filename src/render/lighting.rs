@@ -0,0 +1,70 @@
+use crate::types::Vec3;
+
+/// Fonte de luz pontual.
+#[derive(Clone, Debug)]
+pub struct Light {
+    /// Posição da lâmpada no SRU.
+    pub position: Vec3,
+    /// Intensidade da luz ambiente (RGB, de 0 a 255).
+    pub ambient: Vec3,
+    /// Intensidade da lâmpada (RGB, de 0 a 255).
+    pub diffuse: Vec3,
+}
+
+/// Coeficientes de reflexão do material das superfícies.
+#[derive(Clone, Debug)]
+pub struct Material {
+    /// Coeficiente de reflexão ambiente (RGB).
+    pub ka: Vec3,
+    /// Coeficiente de reflexão difusa (RGB).
+    pub kd: Vec3,
+    /// Coeficiente de reflexão especular (RGB).
+    pub ks: Vec3,
+    /// Expoente de especularidade.
+    pub shininess: f32,
+}
+
+/// Calcula a intensidade refletida em um ponto pelo modelo ambiente + difusa + especular.
+///
+/// `normal` deve estar normalizado e `eye` é a posição do observador (VRP).
+pub fn illuminate(
+    light: &Light,
+    material: &Material,
+    point: &Vec3,
+    normal: &Vec3,
+    eye: &Vec3,
+) -> Vec3 {
+    let ambient: Vec3 = light.ambient.component_mul(&material.ka);
+
+    let l: Vec3 = (light.position - point).normalize();
+    let n_dot_l = normal.dot(&l);
+
+    // A lâmpada está atrás da superfície, resta apenas a componente ambiente
+    if n_dot_l <= 0.0 {
+        return ambient;
+    }
+
+    let diffuse: Vec3 = light.diffuse.component_mul(&material.kd) * n_dot_l;
+
+    let r: Vec3 = (2.0 * n_dot_l * normal - l).normalize();
+    let s: Vec3 = (eye - point).normalize();
+    let r_dot_s = r.dot(&s);
+
+    let specular: Vec3 = if r_dot_s > 0.0 {
+        light.diffuse.component_mul(&material.ks) * r_dot_s.powf(material.shininess)
+    } else {
+        Vec3::zeros()
+    };
+
+    ambient + diffuse + specular
+}
+
+/// Converte uma intensidade RGB (de 0 a 255) para uma cor do buffer de quadro.
+#[inline(always)]
+pub fn intensity_to_color(intensity: &Vec3) -> [u8; 3] {
+    [
+        intensity[0].clamp(0.0, 255.0).round() as u8,
+        intensity[1].clamp(0.0, 255.0).round() as u8,
+        intensity[2].clamp(0.0, 255.0).round() as u8,
+    ]
+}
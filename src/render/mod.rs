@@ -0,0 +1,1050 @@
+pub mod clipping;
+pub mod frame_buffer;
+pub mod lighting;
+
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+
+use ordered_float::OrderedFloat;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use crate::camera::Camera;
+use crate::object::Object;
+use crate::render::clipping::{clip_polygon, ClipVertex};
+use crate::render::frame_buffer::FrameBuffer;
+use crate::render::lighting::{illuminate, intensity_to_color, Light, Material};
+use crate::types::{Mat4, Mat4x1, Vec3};
+use crate::utils::{mat4x1_to_vec3, vec3_to_mat4x1};
+
+/// Cor utilizada no preenchimento das faces.
+const FACE_COLOR: [u8; 3] = [200, 200, 200];
+/// Cor utilizada no desenho das arestas (wireframe).
+const EDGE_COLOR: [u8; 3] = [255, 255, 255];
+/// Tolerância de profundidade das arestas na remoção de linhas ocultas.
+const HIDDEN_LINE_DEPTH_BIAS: f32 = 0.05;
+/// Menor dimensão permitida da window ao escalá-la (zoom da projeção ortográfica).
+const MIN_WINDOW_EXTENT: f32 = 0.01;
+/// Maior dimensão permitida da window ao escalá-la (zoom da projeção ortográfica).
+const MAX_WINDOW_EXTENT: f32 = 10000.0;
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum ProjectionType {
+    Orthographic,
+    Perspective,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum ShaderType {
+    Wireframe,
+    Constant,
+    Gouraud,
+    Phong,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Window {
+    pub xmin: f32,
+    pub xmax: f32,
+    pub ymin: f32,
+    pub ymax: f32,
+}
+
+impl Window {
+    /// Escala a window em torno do seu centro. O fator é limitado para que a largura e a
+    /// altura fiquem entre `MIN_WINDOW_EXTENT` e `MAX_WINDOW_EXTENT`, assim como o dolly da
+    /// câmera é limitado pelo plano próximo.
+    pub fn scale(&mut self, factor: f32) {
+        let width = self.xmax - self.xmin;
+        let height = self.ymax - self.ymin;
+        let factor = factor
+            .max(MIN_WINDOW_EXTENT / width.min(height))
+            .min(MAX_WINDOW_EXTENT / width.max(height));
+
+        let cx = (self.xmax + self.xmin) / 2.0;
+        let cy = (self.ymax + self.ymin) / 2.0;
+        let hw = width / 2.0 * factor;
+        let hh = height / 2.0 * factor;
+
+        self.xmin = cx - hw;
+        self.xmax = cx + hw;
+        self.ymin = cy - hh;
+        self.ymax = cy + hh;
+    }
+
+    /// Expande a window em torno do seu centro até a proporção (largura / altura) informada,
+    /// mantendo visível toda a região original.
+    pub fn fit_aspect(&mut self, aspect: f32) {
+        let cx = (self.xmax + self.xmin) / 2.0;
+        let cy = (self.ymax + self.ymin) / 2.0;
+        let mut hw = (self.xmax - self.xmin) / 2.0;
+        let mut hh = (self.ymax - self.ymin) / 2.0;
+
+        if hw / hh < aspect {
+            hw = hh * aspect;
+        } else {
+            hh = hw / aspect;
+        }
+
+        self.xmin = cx - hw;
+        self.xmax = cx + hw;
+        self.ymin = cy - hh;
+        self.ymax = cy + hh;
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Viewport {
+    pub umin: f32,
+    pub umax: f32,
+    pub vmin: f32,
+    pub vmax: f32,
+}
+
+/// Vértice de uma face em coordenadas de tela (SRT) com os atributos a serem interpolados.
+#[derive(Clone, Copy, Debug)]
+pub struct ScreenVertex {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    /// Intensidade RGB calculada no vértice (sombreamento Gouraud).
+    pub color: Vec3,
+    /// Vetor normal do vértice (sombreamento Phong).
+    pub normal: Vec3,
+    /// Posição do vértice no SRU (sombreamento Phong).
+    pub position: Vec3,
+    /// Indica se a aresta até o próximo vértice deve ser desenhada no modo aramado.
+    pub edge: bool,
+}
+
+/// Interseção de uma aresta com uma linha horizontal de varredura.
+#[derive(Clone, Copy, Debug)]
+pub struct Intersection {
+    /// Coordenada x da interseção.
+    pub x: f32,
+    /// Profundidade interpolada na interseção.
+    pub z: f32,
+    /// Intensidade RGB interpolada na interseção.
+    pub color: Vec3,
+    /// Vetor normal interpolado (não normalizado) na interseção.
+    pub normal: Vec3,
+    /// Posição no SRU interpolada na interseção.
+    pub position: Vec3,
+}
+
+impl From<&ScreenVertex> for Intersection {
+    fn from(vertex: &ScreenVertex) -> Self {
+        Self {
+            x: vertex.x,
+            z: vertex.z,
+            color: vertex.color,
+            normal: vertex.normal,
+            position: vertex.position,
+        }
+    }
+}
+
+impl Intersection {
+    /// Interpola linearmente todos os atributos entre `self` (t = 0) e `other` (t = 1).
+    #[inline(always)]
+    pub fn lerp(&self, other: &Self, t: f32) -> Self {
+        Self {
+            x: self.x + (other.x - self.x) * t,
+            z: self.z + (other.z - self.z) * t,
+            color: self.color + (other.color - self.color) * t,
+            normal: self.normal + (other.normal - self.normal) * t,
+            position: self.position + (other.position - self.position) * t,
+        }
+    }
+}
+
+/// Matrizes do pipeline de visualização, recalculadas sob demanda.
+#[derive(Clone, Copy, Debug)]
+pub struct Pipeline {
+    /// Transformação do SRU para o SRC.
+    pub m_sru_src: Mat4,
+    /// Projeção (perspectiva ou paralela) no SRC.
+    pub m_proj: Mat4,
+    /// Transformação da window para a viewport.
+    pub m_jp: Mat4,
+    /// Transformação completa do SRU para o SRT.
+    pub m_sru_srt: Mat4,
+    /// Transformação do SRU para o volume de visão canônico.
+    pub m_sru_can: Mat4,
+}
+
+#[derive(Clone)]
+pub struct Render {
+    shader_type: ShaderType,
+    projection_type: ProjectionType,
+    camera: Camera,
+    window: Window,
+    viewport: Viewport,
+    light: Light,
+    material: Material,
+    /// Remove as linhas ocultas no modo wireframe.
+    hidden_lines: bool,
+    /// Matrizes do pipeline. São descartadas sempre que câmera, window, viewport ou
+    /// projeção mudam, e recalculadas no próximo acesso.
+    pipeline: OnceLock<Pipeline>,
+}
+
+impl Default for Render {
+    fn default() -> Self {
+        let projection_type = ProjectionType::Orthographic;
+        let shader_type = ShaderType::Wireframe;
+
+        let camera = Camera {
+            vrp: Vec3::new(25.0, 15.0, 80.0),
+            p: Vec3::new(20.0, 10.0, 25.0),
+            y: Vec3::new(0.0, 1.0, 0.0),
+            dp: 40.0,
+            near: 1.0,
+            far: 200.0,
+        };
+        let window = Window {
+            xmin: -20.0,
+            xmax: 20.0,
+            ymin: -15.0,
+            ymax: 15.0,
+        };
+        let viewport = Viewport {
+            umin: 0.0,
+            umax: 299.0,
+            vmin: 0.0,
+            vmax: 199.0,
+        };
+        let light = Light {
+            position: Vec3::new(70.0, 20.0, 35.0),
+            ambient: Vec3::new(120.0, 120.0, 120.0),
+            diffuse: Vec3::new(150.0, 150.0, 150.0),
+        };
+        let material = Material {
+            ka: Vec3::new(0.4, 0.4, 0.6),
+            kd: Vec3::new(0.6, 0.6, 0.9),
+            ks: Vec3::new(0.5, 0.5, 0.5),
+            shininess: 4.0,
+        };
+
+        Self {
+            projection_type,
+            shader_type,
+            camera,
+            window,
+            viewport,
+            light,
+            material,
+            hidden_lines: false,
+            pipeline: OnceLock::new(),
+        }
+    }
+}
+
+impl Render {
+    pub fn set_camera(&mut self, camera: Camera) {
+        self.camera = camera;
+        self.pipeline.take();
+    }
+
+    pub fn set_window(&mut self, window: Window) {
+        self.window = window;
+        self.pipeline.take();
+    }
+
+    pub fn set_viewport(&mut self, viewport: Viewport) {
+        self.viewport = viewport;
+        self.pipeline.take();
+    }
+
+    pub fn set_light(&mut self, light: Light) {
+        self.light = light;
+    }
+
+    pub fn set_material(&mut self, material: Material) {
+        self.material = material;
+    }
+
+    pub fn set_projection(&mut self, projection_type: ProjectionType) {
+        self.projection_type = projection_type;
+        self.pipeline.take();
+    }
+
+    pub fn set_shader(&mut self, shader_type: ShaderType) {
+        self.shader_type = shader_type;
+    }
+
+    pub fn set_hidden_lines(&mut self, hidden_lines: bool) {
+        self.hidden_lines = hidden_lines;
+    }
+
+    /// Orbita a câmera ao redor de P (ângulos em radianos).
+    pub fn orbit(&mut self, yaw: f32, pitch: f32) {
+        self.camera.orbit(yaw, pitch);
+        self.pipeline.take();
+    }
+
+    /// Desloca a câmera de forma que a cena acompanhe um deslocamento em pixels na tela.
+    pub fn pan(&mut self, dx: f32, dy: f32) {
+        let (pixel_width, pixel_height) = self.calc_pixel_size(&self.camera.p);
+        self.camera.pan(-dx * pixel_width, dy * pixel_height);
+        self.pipeline.take();
+    }
+
+    /// Aproxima (fator < 1) ou afasta (fator > 1) a câmera. Na perspectiva, altera a distância
+    /// do VRP a P; na projeção paralela, altera as dimensões da window.
+    pub fn zoom(&mut self, factor: f32) {
+        match self.projection_type {
+            ProjectionType::Perspective => self.camera.dolly(factor),
+            ProjectionType::Orthographic => self.window.scale(factor),
+        }
+        self.pipeline.take();
+    }
+
+    /// Converte um deslocamento em pixels na tela para um deslocamento no SRU, paralelo ao
+    /// plano de projeção e na profundidade do ponto.
+    pub fn calc_drag_delta(&self, point: &Vec3, dx: f32, dy: f32) -> Vec3 {
+        let (un, vn, _) = self.camera.basis();
+        let (pixel_width, pixel_height) = self.calc_pixel_size(point);
+        un * (dx * pixel_width) - vn * (dy * pixel_height)
+    }
+
+    /// Calcula a largura e a altura, em unidades do SRU, de um pixel no plano paralelo ao
+    /// plano de projeção que contém o ponto.
+    fn calc_pixel_size(&self, point: &Vec3) -> (f32, f32) {
+        let pixel_width = (self.window.xmax - self.window.xmin) / (self.viewport.umax - self.viewport.umin);
+        let pixel_height = (self.window.ymax - self.window.ymin) / (self.viewport.vmax - self.viewport.vmin);
+
+        match self.projection_type {
+            ProjectionType::Perspective => {
+                let (_, _, nn) = self.camera.basis();
+                let scale = (self.camera.vrp - point).dot(&nn) / self.camera.dp;
+                (pixel_width * scale, pixel_height * scale)
+            }
+            ProjectionType::Orthographic => (pixel_width, pixel_height),
+        }
+    }
+
+    /// Retorna as matrizes do pipeline, recalculando-as caso algum parâmetro tenha mudado.
+    pub fn get_pipeline(&self) -> &Pipeline {
+        self.pipeline.get_or_init(|| {
+            Render::calc_pipeline(&self.camera, &self.window, &self.viewport, &self.projection_type)
+        })
+    }
+
+    pub fn get_m_sru_srt(&self) -> Mat4 {
+        self.get_pipeline().m_sru_srt
+    }
+
+    pub fn get_m_sru_src(&self) -> Mat4 {
+        self.get_pipeline().m_sru_src
+    }
+
+    pub fn get_m_proj(&self) -> Mat4 {
+        self.get_pipeline().m_proj
+    }
+
+    pub fn get_m_jp(&self) -> Mat4 {
+        self.get_pipeline().m_jp
+    }
+
+    pub fn get_camera(&self) -> &Camera {
+        &self.camera
+    }
+
+    pub fn get_window(&self) -> &Window {
+        &self.window
+    }
+
+    pub fn get_viewport(&self) -> &Viewport {
+        &self.viewport
+    }
+
+    pub fn get_projection(&self) -> ProjectionType {
+        self.projection_type
+    }
+
+    pub fn get_shader(&self) -> ShaderType {
+        self.shader_type
+    }
+
+    pub fn get_hidden_lines(&self) -> bool {
+        self.hidden_lines
+    }
+
+    #[inline(always)]
+    fn calc_sru_src_matrix(camera: &Camera, nn: &Vec3) -> Mat4 {
+        let v: Vec3 = camera.y - (camera.y.dot(nn) * nn);
+        let vn: Vec3 = v.normalize();
+        let un: Vec3 = vn.cross(nn);
+
+        let m14 = -camera.vrp.dot(&un);
+        let m24 = -camera.vrp.dot(&vn);
+        let m34 = -camera.vrp.dot(nn);
+
+        Mat4::new(
+            un[0], un[1], un[2], m14,
+            vn[0], vn[1], vn[2], m24,
+            nn[0], nn[1], nn[2], m34,
+            0.0, 0.0, 0.0, 1.0,
+        )
+    }
+
+    #[inline(always)]
+    fn calc_pers_matrix(camera: &Camera, m_sru_src: &Mat4, nn: &Vec3) -> Mat4 {
+        let vp: Vec3 = camera.vrp + (camera.dp * (-nn));
+        let src_vp: Mat4x1 = m_sru_src * vec3_to_mat4x1(&vp);
+        let src_prp: Mat4x1 = m_sru_src * vec3_to_mat4x1(&camera.vrp);
+
+        let m33 = -src_vp[2] / camera.dp;
+        let m34 = src_vp[2] * (src_prp[2] / camera.dp);
+        let m43 = -1.0 / camera.dp;
+        let m44 = src_prp[2] / camera.dp;
+
+        Mat4::new(
+            1.0, 0.0, 0.0, 0.0,
+            0.0, 1.0, 0.0, 0.0,
+            0.0, 0.0, m33, m34,
+            0.0, 0.0, m43, m44,
+        )
+    }
+
+    /// Projeção paralela ortográfica sobre o plano de projeção. A coordenada z do SRC é
+    /// mantida para o teste de profundidade.
+    #[inline(always)]
+    fn calc_orth_matrix() -> Mat4 {
+        Mat4::identity()
+    }
+
+    #[inline(always)]
+    fn calc_jp_matrix(window: &Window, viewport: &Viewport) -> Mat4 {
+        let m11 = (viewport.umax - viewport.umin) / (window.xmax - window.xmin);
+        let m14 = -window.xmin * m11 + viewport.umin;
+        let m22 = (viewport.vmin - viewport.vmax) / (window.ymax - window.ymin);
+        let m24 = window.ymin * ((viewport.vmax - viewport.vmin) / (window.ymax - window.ymin)) + viewport.vmax;
+
+        Mat4::new(
+            m11, 0.0, 0.0, m14,
+            0.0, m22, 0.0, m24,
+            0.0, 0.0, 1.0, 0.0,
+            0.0, 0.0, 0.0, 1.0,
+        )
+    }
+
+    /// Calcula todas as matrizes do pipeline de visualização.
+    fn calc_pipeline(
+        camera: &Camera,
+        window: &Window,
+        viewport: &Viewport,
+        projection_type: &ProjectionType,
+    ) -> Pipeline {
+        let n: Vec3 = camera.vrp - camera.p;
+        let nn: Vec3 = n.normalize();
+
+        let m_sru_src: Mat4 = Render::calc_sru_src_matrix(camera, &nn);
+        let m_proj: Mat4 = match projection_type {
+            ProjectionType::Orthographic => Render::calc_orth_matrix(),
+            ProjectionType::Perspective => Render::calc_pers_matrix(camera, &m_sru_src, &nn),
+        };
+        let m_jp: Mat4 = Render::calc_jp_matrix(window, viewport);
+        let m_norm: Mat4 = Render::calc_norm_matrix(camera, window, projection_type);
+
+        Pipeline {
+            m_sru_src,
+            m_proj,
+            m_jp,
+            m_sru_srt: m_jp * (m_proj * m_sru_src),
+            m_sru_can: m_norm * m_sru_src,
+        }
+    }
+
+    /// Calcula o vetor normal unitário da face.
+    ///
+    /// Nos quadriláteros, a normal é o produto vetorial das diagonais, que continua definido
+    /// quando dois vértices vizinhos coincidem (como nos polos da esfera). Nos triângulos, é
+    /// calculada a partir das arestas.
+    #[inline(always)]
+    pub fn calc_face_normal(vertices: &[Mat4x1], face: &[usize]) -> Vec3 {
+        let a: Vec3 = mat4x1_to_vec3(&vertices[face[0]]);
+        let b: Vec3 = mat4x1_to_vec3(&vertices[face[1]]);
+        let c: Vec3 = mat4x1_to_vec3(&vertices[face[2]]);
+
+        if let Some(&d) = face.get(3) {
+            let d: Vec3 = mat4x1_to_vec3(&vertices[d]);
+            let ac: Vec3 = c - a;
+            let bd: Vec3 = d - b;
+            return ac.cross(&bd).normalize();
+        }
+
+        let bc: Vec3 = c - b;
+        let ba: Vec3 = a - b;
+        bc.cross(&ba).normalize()
+    }
+
+    /// Calcula o centróide da face.
+    #[inline(always)]
+    fn calc_face_centroid(vertices: &[Mat4x1], face: &[usize]) -> Vec3 {
+        face
+            .iter()
+            .map(|&i| mat4x1_to_vec3(&vertices[i]))
+            .sum::<Vec3>() / face.len() as f32
+    }
+
+    /// Calcula a cor de uma face no sombreamento constante, avaliando a iluminação no centróide.
+    fn calc_constant_color(&self, vertices: &[Mat4x1], face: &[usize]) -> [u8; 3] {
+        let normal: Vec3 = Render::calc_face_normal(vertices, face);
+        let centroid: Vec3 = Render::calc_face_centroid(vertices, face);
+        let intensity: Vec3 = illuminate(&self.light, &self.material, &centroid, &normal, &self.camera.vrp);
+        intensity_to_color(&intensity)
+    }
+
+    /// Filtra os vértices que não são vizíveis através do vetor normal das faces.
+    fn apply_visibility_filter<const N: usize>(&self, vertices: &[Mat4x1], faces: &[[usize; N]]) -> Vec<[usize; N]> {
+        let camera: &Camera = &self.camera;
+
+        faces
+            .iter()
+            .filter_map(|face| {
+                let a: Vec3 = mat4x1_to_vec3(&vertices[face[0]]);
+                let b: Vec3 = mat4x1_to_vec3(&vertices[face[1]]);
+                let c: Vec3 = mat4x1_to_vec3(&vertices[face[2]]);
+
+                let nn: Vec3 = Render::calc_face_normal(vertices, face);
+
+                // Na projeção paralela, todos os raios de projeção têm a direção de n
+                let on: Vec3 = match self.projection_type {
+                    ProjectionType::Orthographic => (camera.vrp - camera.p).normalize(),
+                    ProjectionType::Perspective => {
+                        let cent: Vec3 = (a + b + c) / 3.0;
+                        (camera.vrp - cent).normalize()
+                    }
+                };
+
+                if nn.dot(&on) > 0.0 {
+                    Some(*face)
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// Calcula a matriz de normalização do SRC para o volume de visão canônico, onde é
+    /// realizado o recorte.
+    fn calc_norm_matrix(camera: &Camera, window: &Window, projection_type: &ProjectionType) -> Mat4 {
+        let cx = (window.xmax + window.xmin) / 2.0;
+        let cy = (window.ymax + window.ymin) / 2.0;
+        let hw = (window.xmax - window.xmin) / 2.0;
+        let hh = (window.ymax - window.ymin) / 2.0;
+
+        match projection_type {
+            ProjectionType::Perspective => {
+                // Cisalha o centro da window para o eixo z e escala o tronco de pirâmide
+                // para faces a 45° com o plano distante em z = -1
+                let m_sh = Mat4::new(
+                    1.0, 0.0, cx / camera.dp, 0.0,
+                    0.0, 1.0, cy / camera.dp, 0.0,
+                    0.0, 0.0, 1.0, 0.0,
+                    0.0, 0.0, 0.0, 1.0,
+                );
+                let m_s = Mat4::new(
+                    camera.dp / (hw * camera.far), 0.0, 0.0, 0.0,
+                    0.0, camera.dp / (hh * camera.far), 0.0, 0.0,
+                    0.0, 0.0, 1.0 / camera.far, 0.0,
+                    0.0, 0.0, 0.0, 1.0,
+                );
+                m_s * m_sh
+            }
+            ProjectionType::Orthographic => {
+                // Leva a window para [-1, 1] e os planos próximo e distante para z = 0 e z = -1
+                let depth = camera.far - camera.near;
+                Mat4::new(
+                    1.0 / hw, 0.0, 0.0, -cx / hw,
+                    0.0, 1.0 / hh, 0.0, -cy / hh,
+                    0.0, 0.0, 1.0 / depth, camera.near / depth,
+                    0.0, 0.0, 0.0, 1.0,
+                )
+            }
+        }
+    }
+
+    /// Aplica as transformações de SRU para SRT aos vértices de um polígono recortado.
+    fn apply_screen_transforns(&self, polygon: &[ClipVertex]) -> Vec<ScreenVertex> {
+        let m_sru_srt: Mat4 = self.get_m_sru_srt();
+
+        polygon
+            .iter()
+            .map(|vertex| {
+                let vertex_srt: Mat4x1 = m_sru_srt * vec3_to_mat4x1(&vertex.position);
+                ScreenVertex {
+                    x: (vertex_srt[0] / vertex_srt[3]).round(),
+                    y: (vertex_srt[1] / vertex_srt[3]).round(),
+                    z: vertex_srt[2],
+                    color: vertex.color,
+                    normal: vertex.normal,
+                    position: vertex.position,
+                    edge: vertex.edge,
+                }
+            })
+            .collect()
+    }
+
+    /// Calcula os vetores normais dos vértices pela média das normais das faces adjacentes.
+    pub fn calc_vertex_normals<const N: usize>(vertices: &[Mat4x1], faces: &[[usize; N]]) -> Vec<Vec3> {
+        let mut normals: Vec<Vec3> = vec![Vec3::zeros(); vertices.len()];
+
+        for face in faces {
+            let normal: Vec3 = Render::calc_face_normal(vertices, face);
+            if normal.iter().any(|c| c.is_nan()) {
+                continue;
+            }
+            for &i in face {
+                normals[i] += normal;
+            }
+        }
+
+        normals
+            .into_iter()
+            .map(|normal| normal.try_normalize(f32::EPSILON).unwrap_or_else(Vec3::zeros))
+            .collect()
+    }
+
+    /// Calcula a intensidade RGB de cada vértice (sombreamento Gouraud).
+    fn calc_vertex_colors(&self, vertices: &[Mat4x1], normals: &[Vec3]) -> Vec<Vec3> {
+        vertices
+            .par_iter()
+            .zip(normals.par_iter())
+            .map(|(vertex, normal)| {
+                let position: Vec3 = mat4x1_to_vec3(vertex);
+                illuminate(&self.light, &self.material, &position, normal, &self.camera.vrp)
+            })
+            .collect()
+    }
+
+    /// Calcula as interseções das arestas do polígono com as linhas horizontais de varredura.
+    ///
+    /// O polígono é fechado (a última aresta liga o último vértice ao primeiro). Cada aresta
+    /// contribui nas linhas `y` tais que `ceil(ymin) <= y < ceil(ymax)`, de modo que arestas
+    /// horizontais são ignoradas e vértices sobre uma linha de varredura são contados uma única
+    /// vez quando a borda passa por eles (ou zero/duas vezes em extremos locais), mantendo sempre
+    /// um número par de interseções por linha. Apenas as linhas em `0..height` são geradas.
+    pub fn calculate_intersections(
+        polygon: &[ScreenVertex],
+        height: usize,
+    ) -> BTreeMap<usize, Vec<Intersection>> {
+        let mut intersections: BTreeMap<usize, Vec<Intersection>> = BTreeMap::new();
+
+        for (i, v0) in polygon.iter().enumerate() {
+            let v1 = &polygon[(i + 1) % polygon.len()];
+            let (v0, v1) = if v0.y > v1.y { (v1, v0) } else { (v0, v1) };
+
+            let dy = v1.y - v0.y;
+            if dy == 0.0 || !dy.is_finite() {
+                continue;
+            }
+
+            let start = Intersection::from(v0);
+            let end = Intersection::from(v1);
+
+            let y_initial = v0.y.ceil().max(0.0);
+            let y_final = v1.y.ceil().min(height as f32);
+
+            let mut y = y_initial;
+            while y < y_final {
+                intersections.entry(y as usize)
+                    .or_default()
+                    .push(start.lerp(&end, (y - v0.y) / dy));
+                y += 1.0;
+            }
+        }
+
+        for (_, intersections) in intersections.iter_mut() {
+            intersections.sort_by_key(|intersection| OrderedFloat(intersection.x));
+        }
+
+        intersections
+    }
+
+    /// Percorre os pixels internos do polígono dentro da viewport, chamando `paint` com as
+    /// coordenadas de tela e os atributos interpolados em cada pixel.
+    fn scan_polygon(&self, polygon: &[ScreenVertex], mut paint: impl FnMut(f32, f32, &Intersection)) {
+        let height = self.viewport.vmax.max(0.0) as usize + 1;
+        let width = self.viewport.umax.max(0.0) as i64 + 1;
+
+        // Para cada linha, preenche os pixels entre os pares de interseções
+        for (y, x_intersections) in Render::calculate_intersections(polygon, height) {
+            let y = y as f32;
+
+            for span in x_intersections.chunks_exact(2) {
+                let (start, end) = (span[0], span[1]);
+                let x_initial = (start.x.ceil() as i64).max(0);
+                let x_final = (end.x.ceil() as i64).min(width);
+                let dx = end.x - start.x;
+
+                // Desenho (ou pintura) linha a linha
+                for x in x_initial..x_final {
+                    let x = x as f32;
+                    let t = if dx != 0.0 { (x - start.x) / dx } else { 0.0 };
+                    paint(x, y, &start.lerp(&end, t));
+                }
+            }
+        }
+    }
+
+    /// Rasteriza um segmento de reta pelo algoritmo DDA, chamando `paint` com as coordenadas
+    /// de tela e a profundidade interpolada em cada pixel.
+    fn scan_line(v0: &ScreenVertex, v1: &ScreenVertex, mut paint: impl FnMut(f32, f32, f32)) {
+        let dx = v1.x - v0.x;
+        let dy = v1.y - v0.y;
+        let dz = v1.z - v0.z;
+        let steps = dx.abs().max(dy.abs()).round().max(1.0);
+
+        let tx = dx / steps;
+        let ty = dy / steps;
+        let tz = dz / steps;
+
+        let mut x = v0.x;
+        let mut y = v0.y;
+        let mut z = v0.z;
+
+        for _ in 0..=steps as usize {
+            paint(x.round(), y.round(), z);
+            x += tx;
+            y += ty;
+            z += tz;
+        }
+    }
+
+    /// Desenha as arestas das faces visíveis. No modo de remoção de linhas ocultas, as faces
+    /// são antes preenchidas apenas no buffer de profundidade e as arestas são testadas contra ele.
+    /// As arestas criadas pelo recorte ao longo dos planos do volume de visão não são desenhadas.
+    fn render_wireframe(&self, frame_buffer: &mut FrameBuffer, polygons: &[Vec<ScreenVertex>]) {
+        if self.hidden_lines {
+            for polygon in polygons {
+                self.scan_polygon(polygon, |x, y, fragment| {
+                    frame_buffer.set_depth(x, y, -fragment.z);
+                });
+            }
+        }
+
+        for polygon in polygons {
+            for (i, v0) in polygon.iter().enumerate().filter(|(_, v0)| v0.edge) {
+                let v1 = &polygon[(i + 1) % polygon.len()];
+
+                Render::scan_line(v0, v1, |x, y, z| {
+                    if self.hidden_lines {
+                        frame_buffer.set_pixel(x, y, -z - HIDDEN_LINE_DEPTH_BIAS, EDGE_COLOR);
+                    } else {
+                        frame_buffer.set_color(x, y, EDGE_COLOR);
+                    }
+                });
+            }
+        }
+    }
+
+    /// Rasteriza as faces visíveis no buffer de quadro, com os vetores normais dos vértices
+    /// usados pelos sombreamentos Gouraud e Phong.
+    ///
+    /// As faces podem ser quadriláteros (`N = 4`) ou triângulos (`N = 3`).
+    pub fn render<const N: usize>(
+        &self,
+        frame_buffer: &mut FrameBuffer,
+        vertices: &[Mat4x1],
+        vertex_normals: &[Vec3],
+        faces: &[[usize; N]],
+    ) {
+        let visible_faces: Vec<[usize; N]> = self.apply_visibility_filter(vertices, faces);
+
+        let vertex_colors: Vec<Vec3> = match self.shader_type {
+            ShaderType::Gouraud => self.calc_vertex_colors(vertices, vertex_normals),
+            _ => vec![Vec3::zeros(); vertices.len()],
+        };
+
+        let m_sru_can: Mat4 = self.get_pipeline().m_sru_can;
+        let zmin = -self.camera.near / self.camera.far;
+
+        // Recorta as faces visíveis no volume canônico antes de levá-las para a tela
+        let mut clipped_faces: Vec<[usize; N]> = Vec::with_capacity(visible_faces.len());
+        let mut polygons: Vec<Vec<ScreenVertex>> = Vec::with_capacity(visible_faces.len());
+
+        for face in visible_faces {
+            let polygon: Vec<ClipVertex> = face
+                .iter()
+                .map(|&i| ClipVertex {
+                    point: (m_sru_can * vertices[i]).xyz(),
+                    position: mat4x1_to_vec3(&vertices[i]),
+                    color: vertex_colors[i],
+                    normal: vertex_normals[i],
+                    edge: true,
+                })
+                .collect();
+
+            let polygon: Vec<ClipVertex> = clip_polygon(polygon, &self.projection_type, zmin);
+            if polygon.len() < 3 {
+                continue;
+            }
+
+            clipped_faces.push(face);
+            polygons.push(self.apply_screen_transforns(&polygon));
+        }
+
+        if self.shader_type == ShaderType::Wireframe {
+            self.render_wireframe(frame_buffer, &polygons);
+            return;
+        }
+
+        for (face, polygon) in clipped_faces.iter().zip(&polygons) {
+            let face_color: [u8; 3] = match self.shader_type {
+                ShaderType::Constant => self.calc_constant_color(vertices, face),
+                _ => FACE_COLOR,
+            };
+
+            self.scan_polygon(polygon, |x, y, fragment| {
+                // A profundidade é a distância ao longo da direção de visão (-z no SRC)
+                let depth = -fragment.z;
+
+                match self.shader_type {
+                    ShaderType::Wireframe => {}
+                    ShaderType::Constant => {
+                        // Pinta a face com cor constante
+                        frame_buffer.set_pixel(x, y, depth, face_color);
+                    }
+                    ShaderType::Gouraud => {
+                        // Pinta a face com a interpolação de cores dos vértices
+                        frame_buffer.set_pixel(x, y, depth, intensity_to_color(&fragment.color));
+                    }
+                    ShaderType::Phong => {
+                        // Pinta a face avaliando a iluminação com a normal interpolada,
+                        // somente nos pixels que passariam no teste de profundidade
+                        if !frame_buffer.get_depth(x, y).is_some_and(|d| depth < d) {
+                            return;
+                        }
+                        let normal: Vec3 = fragment.normal
+                            .try_normalize(f32::EPSILON)
+                            .unwrap_or_else(Vec3::zeros);
+                        let intensity: Vec3 = illuminate(
+                            &self.light,
+                            &self.material,
+                            &fragment.position,
+                            &normal,
+                            &self.camera.vrp,
+                        );
+                        frame_buffer.set_pixel(x, y, depth, intensity_to_color(&intensity));
+                    }
+                }
+            });
+        }
+    }
+
+    /// Rasteriza as malhas dos objetos, usando os triângulos quando a triangulação estiver
+    /// habilitada.
+    pub fn render_objects(&self, frame_buffer: &mut FrameBuffer, objects: &[Object]) {
+        for object in objects {
+            let vertices = object.get_vertices();
+            let normals = object.get_normals();
+            match object.get_triangles() {
+                Some(triangles) => self.render(frame_buffer, vertices, normals, triangles),
+                None => self.render(frame_buffer, vertices, normals, object.get_faces()),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quad(points: [(f32, f32); 4]) -> Vec<ScreenVertex> {
+        points
+            .iter()
+            .map(|&(x, y)| ScreenVertex {
+                x,
+                y,
+                z: 0.0,
+                color: Vec3::zeros(),
+                normal: Vec3::zeros(),
+                position: Vec3::zeros(),
+                edge: true,
+            })
+            .collect()
+    }
+
+    /// Interseções de cada linha, como pares (linha, coordenadas x ordenadas).
+    fn rows(polygon: &[ScreenVertex], height: usize) -> Vec<(usize, Vec<f32>)> {
+        Render::calculate_intersections(polygon, height)
+            .into_iter()
+            .map(|(y, intersections)| (y, intersections.iter().map(|i| i.x).collect()))
+            .collect()
+    }
+
+    fn assert_rows(actual: Vec<(usize, Vec<f32>)>, expected: &[(usize, &[f32])]) {
+        assert_eq!(actual.len(), expected.len(), "linhas: {actual:?}");
+        for ((y, xs), &(expected_y, expected_xs)) in actual.iter().zip(expected) {
+            assert_eq!(*y, expected_y);
+            assert_eq!(xs.len(), expected_xs.len(), "linha {y}: {xs:?}");
+            for (x, expected_x) in xs.iter().zip(expected_xs) {
+                assert!((x - expected_x).abs() < 1e-5, "linha {y}: {xs:?} != {expected_xs:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn closing_edge_vertex_on_scanline_and_extrema() {
+        // Losango: a aresta de fechamento (3 → 0) é a única à esquerda nas linhas 2 e 3, o
+        // vértice 0 e o vértice 2 estão sobre a linha 2, o vértice 1 é um mínimo sobre a linha 0
+        // e o vértice 3 é um máximo sobre a linha 4
+        let polygon = quad([(0.0, 2.0), (2.0, 0.0), (4.0, 2.0), (2.0, 4.0)]);
+
+        assert_rows(
+            rows(&polygon, 100),
+            &[
+                (0, &[2.0, 2.0]),
+                (1, &[1.0, 3.0]),
+                (2, &[0.0, 4.0]),
+                (3, &[1.0, 3.0]),
+            ],
+        );
+    }
+
+    #[test]
+    fn horizontal_edges() {
+        // Paralelogramo com arestas horizontais em y = 0 (incluída) e y = 4 (excluída)
+        let polygon = quad([(0.0, 0.0), (4.0, 0.0), (6.0, 4.0), (2.0, 4.0)]);
+
+        assert_rows(
+            rows(&polygon, 100),
+            &[
+                (0, &[0.0, 4.0]),
+                (1, &[0.5, 4.5]),
+                (2, &[1.0, 5.0]),
+                (3, &[1.5, 5.5]),
+            ],
+        );
+    }
+
+    #[test]
+    fn local_extremum_between_scanlines() {
+        // Mínimo em y = 0.5 e máximo em y = 2.5, fora das linhas de varredura
+        let polygon = quad([(0.0, 1.5), (2.0, 0.5), (4.0, 1.5), (2.0, 2.5)]);
+
+        assert_rows(
+            rows(&polygon, 100),
+            &[
+                (1, &[1.0, 3.0]),
+                (2, &[1.0, 3.0]),
+            ],
+        );
+    }
+
+    #[test]
+    fn concave_quad() {
+        // Ponta de flecha com o vértice côncavo (4, 2): as linhas 0 e 1 têm dois segmentos
+        let polygon = quad([(0.0, 0.0), (4.0, 2.0), (8.0, 0.0), (4.0, 6.0)]);
+
+        assert_rows(
+            rows(&polygon, 100),
+            &[
+                (0, &[0.0, 0.0, 8.0, 8.0]),
+                (1, &[2.0 / 3.0, 2.0, 6.0, 22.0 / 3.0]),
+                (2, &[4.0 / 3.0, 20.0 / 3.0]),
+                (3, &[2.0, 6.0]),
+                (4, &[8.0 / 3.0, 16.0 / 3.0]),
+                (5, &[10.0 / 3.0, 14.0 / 3.0]),
+            ],
+        );
+    }
+
+    #[test]
+    fn rows_outside_the_viewport_are_skipped() {
+        // Apenas as linhas 0..height são geradas; coordenadas x fora da tela são mantidas
+        let polygon = quad([(-3.0, -2.0), (12.0, -2.0), (12.0, 3.0), (-3.0, 3.0)]);
+        assert_rows(rows(&polygon, 2), &[(0, &[-3.0, 12.0]), (1, &[-3.0, 12.0])]);
+
+        let above = quad([(0.0, 5.0), (4.0, 5.0), (4.0, 9.0), (0.0, 9.0)]);
+        assert!(rows(&above, 5).is_empty());
+
+        let below = quad([(0.0, -9.0), (4.0, -9.0), (4.0, -1.0), (0.0, -1.0)]);
+        assert!(rows(&below, 5).is_empty());
+    }
+
+    #[test]
+    fn scan_polygon_clips_to_the_viewport() {
+        let mut render = Render::default();
+        render.set_viewport(Viewport {
+            umin: 0.0,
+            umax: 9.0,
+            vmin: 0.0,
+            vmax: 1.0,
+        });
+
+        let polygon = quad([(-3.0, -2.0), (12.0, -2.0), (12.0, 3.0), (-3.0, 3.0)]);
+        let mut pixels: Vec<(f32, f32)> = Vec::new();
+        render.scan_polygon(&polygon, |x, y, _| pixels.push((x, y)));
+
+        let expected: Vec<(f32, f32)> = (0..2)
+            .flat_map(|y| (0..10).map(move |x| (x as f32, y as f32)))
+            .collect();
+        assert_eq!(pixels, expected);
+    }
+
+    #[test]
+    fn orthographic_zoom_is_bounded() {
+        let mut render = Render::default();
+        render.set_projection(ProjectionType::Orthographic);
+        let extent = |render: &Render| {
+            let window = render.get_window();
+            (window.xmax - window.xmin, window.ymax - window.ymin)
+        };
+
+        for _ in 0..10_000 {
+            render.zoom(0.5);
+        }
+        let (width, height) = extent(&render);
+        assert!(width.min(height) >= MIN_WINDOW_EXTENT * 0.999, "{width} x {height}");
+
+        // A window volta a crescer a partir do limite inferior
+        render.zoom(2.0);
+        let (grown, _) = extent(&render);
+        assert!(grown > width);
+
+        for _ in 0..10_000 {
+            render.zoom(2.0);
+        }
+        let (width, height) = extent(&render);
+        assert!(width.max(height) <= MAX_WINDOW_EXTENT * 1.001, "{width} x {height}");
+    }
+
+    #[test]
+    fn perspective_dolly_stays_between_near_and_far() {
+        let mut render = Render::default();
+        let distance = |render: &Render| (render.get_camera().vrp - render.get_camera().p).norm();
+
+        for _ in 0..1000 {
+            render.zoom(0.5);
+        }
+        assert!(distance(&render) > render.get_camera().near);
+
+        for _ in 0..1000 {
+            render.zoom(2.0);
+        }
+        assert!(distance(&render) < render.get_camera().far);
+    }
+
+    #[test]
+    fn collapsed_pole_quads_have_normals() {
+        // Os quadriláteros dos polos da esfera têm dois vértices coincidentes
+        let sphere = crate::conic::Conic::Sphere { radius: 4.0 }.to_object(16, 8).unwrap();
+        let vertices = sphere.get_vertices();
+
+        for face in sphere.get_faces() {
+            let normal = Render::calc_face_normal(vertices, face);
+            assert!(normal.iter().all(|c| c.is_finite()), "face {face:?}");
+
+            // A esfera está centrada na origem: as normais apontam para fora
+            let centroid = Render::calc_face_centroid(vertices, face);
+            assert!(normal.dot(&centroid) > 0.0, "face {face:?}");
+        }
+
+        for (vertex, normal) in vertices.iter().zip(sphere.get_normals()) {
+            assert!(normal.dot(&mat4x1_to_vec3(vertex)) > 0.0);
+        }
+    }
+}
@@ -0,0 +1,268 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use crate::camera::Camera;
+use crate::object::{DiagonalSplit, Object};
+use crate::render::{ProjectionType, Render, ShaderType, Viewport, Window};
+use crate::types::{Mat4x1, Vec3};
+use crate::utils::{mat4x1_to_vec3, weighted_point};
+
+/// Versão atual do formato do arquivo de cena.
+pub const SCENE_VERSION: u32 = 1;
+
+/// Superfície salva no arquivo de cena.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ObjectData {
+    pub ni: usize,
+    pub nj: usize,
+    pub ti: usize,
+    pub tj: usize,
+    pub resi: usize,
+    pub resj: usize,
+    /// Pontos de controle (x, y, z, w), linha a linha: posição cartesiana e peso.
+    pub control_points: Vec<[f32; 4]>,
+    pub knots_i: Vec<f32>,
+    pub knots_j: Vec<f32>,
+    /// Estratégia de triangulação. Ausente nos arquivos que não a salvavam (sem triangulação).
+    #[serde(default)]
+    pub triangulation: Option<DiagonalSplit>,
+}
+
+impl ObjectData {
+    pub fn from_object(object: &Object) -> Self {
+        let (ni, nj) = object.get_size();
+        let (ti, tj) = object.get_orders();
+        let (resi, resj) = object.get_resolution();
+
+        Self {
+            ni,
+            nj,
+            ti,
+            tj,
+            resi,
+            resj,
+            control_points: object
+                .control_points
+                .iter()
+                .map(|point| {
+                    let position: Vec3 = mat4x1_to_vec3(point);
+                    [position.x, position.y, position.z, point.w]
+                })
+                .collect(),
+            knots_i: object.get_knots_i().to_vec(),
+            knots_j: object.get_knots_j().to_vec(),
+            triangulation: object.get_triangulation(),
+        }
+    }
+
+    /// Reconstrói a superfície, validando a grade de pontos de controle e os nós.
+    pub fn to_object(&self) -> Result<Object, String> {
+        let control_points: Vec<Mat4x1> = self
+            .control_points
+            .iter()
+            .map(|&[x, y, z, w]| weighted_point(&Vec3::new(x, y, z), w))
+            .collect();
+
+        let mut object = Object::from_control_points(
+            self.ni,
+            self.nj,
+            self.ti,
+            self.tj,
+            self.resi,
+            self.resj,
+            control_points,
+        )?;
+        object.set_knots(self.knots_i.clone(), self.knots_j.clone())?;
+        object.set_triangulation(self.triangulation);
+
+        Ok(object)
+    }
+}
+
+/// Estado de uma vista (render) salvo no arquivo de cena.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ViewData {
+    pub name: String,
+    pub camera: Camera,
+    pub window: Window,
+    pub viewport: Viewport,
+    pub projection: ProjectionType,
+    pub shader: ShaderType,
+    #[serde(default)]
+    pub hidden_lines: bool,
+}
+
+impl ViewData {
+    pub fn from_render(name: &str, render: &Render) -> Self {
+        Self {
+            name: name.to_string(),
+            camera: render.get_camera().clone(),
+            window: render.get_window().clone(),
+            viewport: render.get_viewport().clone(),
+            projection: render.get_projection(),
+            shader: render.get_shader(),
+            hidden_lines: render.get_hidden_lines(),
+        }
+    }
+
+    /// Aplica o estado salvo ao render. Câmeras e windows inválidas são rejeitadas.
+    pub fn apply(&self, render: &mut Render) -> Result<(), String> {
+        if !self.camera.is_valid() {
+            return Err(format!("Câmera inválida na vista \"{}\".", self.name));
+        }
+        if self.window.xmin >= self.window.xmax || self.window.ymin >= self.window.ymax {
+            return Err(format!("Window inválida na vista \"{}\".", self.name));
+        }
+
+        render.set_camera(self.camera.clone());
+        render.set_window(self.window.clone());
+        render.set_viewport(self.viewport.clone());
+        render.set_projection(self.projection);
+        render.set_shader(self.shader);
+        render.set_hidden_lines(self.hidden_lines);
+
+        Ok(())
+    }
+}
+
+/// Cena completa: superfícies e vistas, salva em JSON.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Scene {
+    pub version: u32,
+    pub objects: Vec<ObjectData>,
+    pub views: Vec<ViewData>,
+}
+
+impl Scene {
+    pub fn new(objects: Vec<ObjectData>, views: Vec<ViewData>) -> Self {
+        Self {
+            version: SCENE_VERSION,
+            objects,
+            views,
+        }
+    }
+
+    /// Reconstrói todas as superfícies da cena.
+    pub fn to_objects(&self) -> Result<Vec<Object>, String> {
+        self.objects.iter().map(ObjectData::to_object).collect()
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("A cena sempre é serializável")
+    }
+
+    /// Lê a cena de um texto JSON, rejeitando versões mais novas que a suportada.
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        let scene: Self = serde_json::from_str(json)
+            .map_err(|error| format!("Arquivo de cena inválido: {error}"))?;
+
+        if scene.version > SCENE_VERSION {
+            return Err(format!(
+                "Versão {} do arquivo de cena não suportada (máxima: {SCENE_VERSION}).",
+                scene.version,
+            ));
+        }
+
+        Ok(scene)
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, self.to_json())
+    }
+
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let json = fs::read_to_string(path)?;
+        Self::from_json(&json).map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::Generator;
+
+    /// Superfície com pesos, nós não uniformes e triangulação.
+    fn object() -> Object {
+        let generator = Generator::Random { seed: 3, amplitude: 4.0 };
+        let mut object = Object::from_generator(&generator, 5, 4, 3, 3, 12, 10).unwrap();
+        object.set_weight(7, 2.5).unwrap();
+        object.set_weight(20, 0.4).unwrap();
+        object
+            .set_knots(
+                vec![0.0, 0.0, 0.0, 0.5, 1.5, 1.75, 3.0, 3.0, 3.0],
+                vec![0.0, 0.0, 0.0, 0.2, 0.6, 1.0, 1.0, 1.0],
+            )
+            .unwrap();
+        object.set_triangulation(Some(DiagonalSplit::Shortest));
+        object
+    }
+
+    fn scene() -> Scene {
+        let mut render = Render::default();
+        render.set_projection(ProjectionType::Orthographic);
+        render.set_shader(ShaderType::Wireframe);
+        render.set_hidden_lines(true);
+        Scene::new(vec![ObjectData::from_object(&object())], vec![ViewData::from_render("Vista", &render)])
+    }
+
+    #[test]
+    fn round_trip_preserves_objects() {
+        let original = object();
+        let objects = Scene::from_json(&scene().to_json()).unwrap().to_objects().unwrap();
+        let loaded = &objects[0];
+
+        assert_eq!(loaded.get_size(), original.get_size());
+        assert_eq!(loaded.get_orders(), original.get_orders());
+        assert_eq!(loaded.get_resolution(), original.get_resolution());
+        assert_eq!(loaded.get_knots_i(), original.get_knots_i());
+        assert_eq!(loaded.get_knots_j(), original.get_knots_j());
+        assert_eq!(loaded.get_triangulation(), original.get_triangulation());
+        assert_eq!(loaded.get_triangles(), original.get_triangles());
+        assert_eq!(loaded.get_weight(7), 2.5);
+        assert_eq!(loaded.get_weight(20), 0.4);
+
+        for (a, b) in loaded.control_points.iter().zip(&original.control_points) {
+            assert!((a - b).norm() < 1e-5, "ponto de controle {a:?} != {b:?}");
+        }
+        for (a, b) in loaded.get_vertices().iter().zip(original.get_vertices()) {
+            assert!((a - b).norm() < 1e-4, "vértice {a:?} != {b:?}");
+        }
+    }
+
+    #[test]
+    fn round_trip_preserves_views() {
+        let scene = Scene::from_json(&scene().to_json()).unwrap();
+        let mut render = Render::default();
+        scene.views[0].apply(&mut render).unwrap();
+
+        assert_eq!(scene.views[0].name, "Vista");
+        assert_eq!(render.get_projection(), ProjectionType::Orthographic);
+        assert_eq!(render.get_shader(), ShaderType::Wireframe);
+        assert!(render.get_hidden_lines());
+    }
+
+    #[test]
+    fn newer_versions_are_rejected() {
+        let mut scene = scene();
+        scene.version = SCENE_VERSION + 1;
+        assert!(Scene::from_json(&scene.to_json()).is_err());
+    }
+
+    #[test]
+    fn files_without_new_fields_still_load() {
+        // Arquivo salvo antes da triangulação e das linhas ocultas
+        let mut json: serde_json::Value = serde_json::from_str(&scene().to_json()).unwrap();
+        json["objects"][0].as_object_mut().unwrap().remove("triangulation");
+        json["views"][0].as_object_mut().unwrap().remove("hidden_lines");
+
+        let scene = Scene::from_json(&json.to_string()).unwrap();
+        assert_eq!(scene.objects[0].triangulation, None);
+        assert!(!scene.views[0].hidden_lines);
+
+        let objects = scene.to_objects().unwrap();
+        assert_eq!(objects[0].get_triangulation(), None);
+        assert_eq!(objects[0].get_knots_i(), object().get_knots_i());
+    }
+}
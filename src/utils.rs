@@ -16,7 +16,7 @@ pub fn mat4x1_to_vec3(mat4x1: &Mat4x1) -> Vec3 {
         Vec3::new(
             mat4x1[0] / mat4x1[3],
             mat4x1[1] / mat4x1[3],
-            mat4x1[2],
+            mat4x1[2] / mat4x1[3],
         )
     } else {
         Vec3::new(
@@ -35,4 +35,28 @@ pub fn vec3_to_mat4x1(vec3: &Vec3) -> Mat4x1 {
         vec3[2],
         1.0,
     )
-}
\ No newline at end of file
+}
+
+/// Ponto em coordenadas homogêneas (w·x, w·y, w·z, w) a partir da posição e do peso.
+#[inline]
+pub fn weighted_point(position: &Vec3, weight: f32) -> Mat4x1 {
+    Mat4x1::new(
+        position[0] * weight,
+        position[1] * weight,
+        position[2] * weight,
+        weight,
+    )
+}
+
+/// Distância, relativa à diagonal da caixa envolvente, abaixo da qual dois vértices são
+/// considerados o mesmo.
+pub const WELD_TOLERANCE: f32 = 1e-5;
+
+/// Tolerância de solda dos pontos: `WELD_TOLERANCE` vezes a diagonal da sua caixa envolvente.
+pub fn weld_tolerance(points: &[Vec3]) -> f32 {
+    let (min, max) = points.iter().fold(
+        (Vec3::repeat(f32::INFINITY), Vec3::repeat(f32::NEG_INFINITY)),
+        |(min, max), p| (min.inf(p), max.sup(p)),
+    );
+    (WELD_TOLERANCE * (max - min).norm()).max(f32::EPSILON)
+}
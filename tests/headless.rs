@@ -0,0 +1,230 @@
+use std::env;
+use std::fs::{self, File};
+use std::path::PathBuf;
+
+use aleluia::generator::Generator;
+use aleluia::headless::{calc_center, render_offscreen};
+use aleluia::object::Object;
+use aleluia::render::frame_buffer::FrameBuffer;
+use aleluia::render::{ProjectionType, Render, ShaderType};
+use aleluia::types::Vec3;
+
+const WIDTH: usize = 160;
+const HEIGHT: usize = 120;
+
+/// Diferença máxima por canal entre a imagem e a referência.
+const CHANNEL_TOLERANCE: u8 = 8;
+
+/// Superfície aleatória de semente fixa, a mesma do subcomando `render --seed 42`.
+fn scene() -> Vec<Object> {
+    let generator = Generator::Random { seed: 42, amplitude: 10.0 };
+    vec![Object::from_generator(&generator, 10, 10, 3, 3, 20, 20).unwrap()]
+}
+
+/// Render padrão com a câmera voltada para o centro dos objetos, como no subcomando `render`.
+fn scene_render(objects: &[Object], projection: ProjectionType, shader: ShaderType) -> Render {
+    let mut render = Render::default();
+    let mut camera = render.get_camera().clone();
+    camera.p = calc_center(objects);
+    render.set_camera(camera);
+    // Aproxima a vista, para que a superfície ocupe boa parte da imagem
+    let mut window = render.get_window().clone();
+    window.scale(0.4);
+    render.set_window(window);
+    render.set_projection(projection);
+    render.set_shader(shader);
+    render
+}
+
+fn render(objects: &[Object], projection: ProjectionType, shader: ShaderType) -> FrameBuffer {
+    render_offscreen(objects, &scene_render(objects, projection, shader), WIDTH, HEIGHT)
+}
+
+fn background() -> [u8; 3] {
+    let frame_buffer = render(&[], ProjectionType::Perspective, ShaderType::Phong);
+    frame_buffer.get_color()[..3].try_into().unwrap()
+}
+
+/// Retângulo (xmin, ymin, xmax, ymax) que contém os pixels diferentes do fundo.
+fn bounds(frame_buffer: &FrameBuffer) -> [usize; 4] {
+    let background = background();
+    let mut bounds = [usize::MAX, usize::MAX, 0, 0];
+    for (k, pixel) in frame_buffer.get_color().chunks_exact(3).enumerate() {
+        if pixel != background {
+            let (x, y) = (k % frame_buffer.width(), k / frame_buffer.width());
+            bounds = [bounds[0].min(x), bounds[1].min(y), bounds[2].max(x), bounds[3].max(y)];
+        }
+    }
+    bounds
+}
+
+/// Compara o buffer com a imagem de referência em `tests/golden`. Com a variável de ambiente
+/// `ALELUIA_BLESS` definida, a referência é regravada a partir do buffer.
+fn assert_golden(name: &str, frame_buffer: &FrameBuffer) {
+    let path: PathBuf = [env!("CARGO_MANIFEST_DIR"), "tests", "golden", &format!("{name}.png")]
+        .iter()
+        .collect();
+
+    if env::var_os("ALELUIA_BLESS").is_some() {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        frame_buffer.save(&path).unwrap();
+        return;
+    }
+
+    let mut reader = png::Decoder::new(File::open(&path).unwrap()).read_info().unwrap();
+    let mut golden = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut golden).unwrap();
+    assert_eq!(
+        (info.width as usize, info.height as usize),
+        (frame_buffer.width(), frame_buffer.height()),
+        "{name}: dimensões diferentes da referência",
+    );
+
+    // Tolera diferenças pequenas de arredondamento, mas no máximo 0,1% dos pixels podem diferir
+    let differing = frame_buffer
+        .get_color()
+        .chunks_exact(3)
+        .zip(golden[..info.buffer_size()].chunks_exact(3))
+        .filter(|(pixel, expected)| {
+            pixel.iter().zip(expected.iter()).any(|(a, b)| a.abs_diff(*b) > CHANNEL_TOLERANCE)
+        })
+        .count();
+    let allowed = frame_buffer.width() * frame_buffer.height() / 1000;
+    assert!(differing <= allowed, "{name}: {differing} pixels diferentes da referência (máximo {allowed})");
+}
+
+#[test]
+fn golden_images() {
+    let objects = scene();
+
+    assert_golden(
+        "perspectiva_phong",
+        &render(&objects, ProjectionType::Perspective, ShaderType::Phong),
+    );
+    assert_golden(
+        "ortografica_constante",
+        &render(&objects, ProjectionType::Orthographic, ShaderType::Constant),
+    );
+
+    let mut wireframe = scene_render(&objects, ProjectionType::Orthographic, ShaderType::Wireframe);
+    wireframe.set_hidden_lines(true);
+    assert_golden(
+        "ortografica_linhas_ocultas",
+        &render_offscreen(&objects, &wireframe, WIDTH, HEIGHT),
+    );
+
+    // Imagem panorâmica, fora da proporção 4:3 da window padrão
+    let wide = scene_render(&objects, ProjectionType::Perspective, ShaderType::Gouraud);
+    assert_golden(
+        "panoramica_gouraud",
+        &render_offscreen(&objects, &wide, 240, 100),
+    );
+}
+
+#[test]
+fn square_stays_square_at_any_aspect() {
+    // Plano quadrado 10 x 10 visto de cima
+    let plane = vec![Object::from_generator(&Generator::Plane { height: 0.0 }, 10, 10, 3, 3, 20, 20).unwrap()];
+
+    for (width, height) in [(160, 120), (160, 160), (320, 120), (100, 300)] {
+        for projection in [ProjectionType::Orthographic, ProjectionType::Perspective] {
+            let mut render = scene_render(&plane, projection, ShaderType::Constant);
+            let mut camera = render.get_camera().clone();
+            camera.vrp = Vec3::new(5.0, 5.0, 60.0);
+            camera.y = Vec3::new(0.0, 1.0, 0.0);
+            render.set_camera(camera);
+
+            let [xmin, ymin, xmax, ymax] = bounds(&render_offscreen(&plane, &render, width, height));
+            let (box_width, box_height) = (xmax - xmin + 1, ymax - ymin + 1);
+            assert!(
+                box_width.abs_diff(box_height) <= 1,
+                "{width} x {height} ({projection:?}): quadrado com {box_width} x {box_height} pixels",
+            );
+        }
+    }
+}
+
+#[test]
+fn camera_is_centered_on_the_surface() {
+    let objects = scene();
+
+    for projection in [ProjectionType::Perspective, ProjectionType::Orthographic] {
+        let [xmin, ymin, xmax, ymax] = bounds(&render(&objects, projection, ShaderType::Constant));
+        assert!(((xmin + xmax) / 2).abs_diff(WIDTH / 2) <= 3);
+        assert!(((ymin + ymax) / 2).abs_diff(HEIGHT / 2) <= 3);
+    }
+}
+
+#[test]
+fn filled_shaders_cover_the_same_pixels() {
+    let objects = scene();
+    let background = background();
+
+    for projection in [ProjectionType::Perspective, ProjectionType::Orthographic] {
+        let masks: Vec<Vec<bool>> = [ShaderType::Constant, ShaderType::Gouraud, ShaderType::Phong]
+            .into_iter()
+            .map(|shader| {
+                render(&objects, projection, shader)
+                    .get_color()
+                    .chunks_exact(3)
+                    .map(|pixel| pixel != background)
+                    .collect()
+            })
+            .collect();
+
+        assert_eq!(masks[0], masks[1]);
+        assert_eq!(masks[0], masks[2]);
+    }
+}
+
+#[test]
+fn rendering_is_deterministic() {
+    let objects = scene();
+    let first = render(&objects, ProjectionType::Perspective, ShaderType::Phong);
+    let second = render(&objects, ProjectionType::Perspective, ShaderType::Phong);
+    assert_eq!(first.get_color(), second.get_color());
+}
+
+#[test]
+fn repeated_renders_keep_the_render_unchanged() {
+    let objects = scene();
+    let render = scene_render(&objects, ProjectionType::Perspective, ShaderType::Gouraud);
+    let extent = |render: &Render| {
+        let window = render.get_window();
+        [window.xmin, window.xmax, window.ymin, window.ymax]
+    };
+    let window = extent(&render);
+
+    // Proporção diferente da window, que precisa ser ajustada a cada imagem
+    let first = render_offscreen(&objects, &render, 240, 100);
+    let second = render_offscreen(&objects, &render, 240, 100);
+    assert_eq!(first.get_color(), second.get_color());
+    assert_eq!(extent(&render), window);
+}
+
+#[test]
+fn saved_images_match_the_frame_buffer() {
+    let frame_buffer = render(&scene(), ProjectionType::Perspective, ShaderType::Gouraud);
+    let directory = env::temp_dir().join(format!("aleluia-headless-{}", std::process::id()));
+    fs::create_dir_all(&directory).unwrap();
+
+    // PPM binário: cabeçalho seguido dos pixels RGB
+    let ppm_path = directory.join("cena.ppm");
+    frame_buffer.save(&ppm_path).unwrap();
+    let ppm = fs::read(&ppm_path).unwrap();
+    let header = format!("P6\n{WIDTH} {HEIGHT}\n255\n");
+    assert_eq!(&ppm[..header.len()], header.as_bytes());
+    assert_eq!(&ppm[header.len()..], frame_buffer.get_color());
+
+    // PNG: os pixels decodificados são os do buffer
+    let png_path = directory.join("cena.png");
+    frame_buffer.save(&png_path).unwrap();
+    let mut reader = png::Decoder::new(File::open(&png_path).unwrap()).read_info().unwrap();
+    let mut pixels = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut pixels).unwrap();
+    assert_eq!((info.width as usize, info.height as usize), (WIDTH, HEIGHT));
+    assert_eq!(info.color_type, png::ColorType::Rgb);
+    assert_eq!(&pixels[..info.buffer_size()], frame_buffer.get_color());
+
+    fs::remove_dir_all(&directory).unwrap();
+}